@@ -3,19 +3,63 @@
 //! be constructed.
 //! Allows for a rudimentary check of path arguments, when/if they are
 //! required to build the resulting URI.
-use std::{borrow::BorrowMut, fmt::{Debug, Display}};
+use std::{borrow::{BorrowMut, Cow}, collections::HashMap, fmt::{Debug, Display}, str::FromStr, sync::Arc};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use ordered_float::OrderedFloat;
+use uri_routes::{encode_path_segment, ApiRouteBuilder, RouteBuilder};
 
-#[derive(Clone, Copy, Debug)]
+/// Determines if, and by whom, an argument is
+/// required. Defaults to `NoOne`.
+///
+/// `All` and `Any` apply to the chain composed
+/// from a node to its tail, and are checked
+/// before the requirements of each node. Where
+/// any node in the chain requires `All`, every
+/// node must have an argument. Where any node
+/// requires `Any`, at least one node must.
+/// ```rust
+/// use uri_resources::ArgRequiredBy;
+/// assert!(ArgRequiredBy::default().is_noone())
+/// ```
+///
+/// ```rust
+/// use uri_resources::{ApiResource, ArgedResource, ArgRequiredBy, LinkedResource, PathComponent};
+/// let chain = |required: ArgRequiredBy, arg: Option<&str>| {
+///     let mut child = ApiResource::<String>::new("child_resource");
+///     child.with_arg_opt(arg.map(String::from));
+///     let mut parent = ApiResource::<String>::new("parent_resource");
+///     parent.with_arg_required(required);
+///     parent.with_child(&mut child).expect("parent node")
+/// };
+///
+/// assert!(chain(ArgRequiredBy::Any, None).compose().is_err());
+/// assert_eq!(chain(ArgRequiredBy::Any, Some("1")).compose().unwrap(), "parent_resource/child_resource/1");
+///
+/// let parent = chain(ArgRequiredBy::All, Some("1"));
+/// assert!(parent.compose().is_err());
+/// assert_eq!(parent.missing_args(), vec!["parent_resource"])
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum ArgRequiredBy {
+    All,
+    Any,
     Child,
     Me,
+    #[default]
     NoOne,
     Parent,
 }
 
 impl ArgRequiredBy {
+    pub fn is_all(self) -> bool {
+        matches!(self, Self::All)
+    }
+
+    pub fn is_any(self) -> bool {
+        matches!(self, Self::Any)
+    }
+
     pub fn is_child(self) -> bool {
         matches!(self, Self::Child)
     }
@@ -33,18 +77,84 @@ impl ArgRequiredBy {
     }
 }
 
+/// Parses a requirement from its name, ignoring
+/// case.
+/// ```rust
+/// use uri_resources::ArgRequiredBy;
+/// assert_eq!("child".parse::<ArgRequiredBy>().unwrap(), ArgRequiredBy::Child);
+/// assert_eq!(ArgRequiredBy::try_from("NoOne").unwrap(), ArgRequiredBy::NoOne);
+/// assert!(ArgRequiredBy::try_from("sibling").is_err())
+/// ```
+impl FromStr for ArgRequiredBy {
+    type Err = ArgError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all"    => Ok(Self::All),
+            "any"    => Ok(Self::Any),
+            "child"  => Ok(Self::Child),
+            "me"     => Ok(Self::Me),
+            "noone"  => Ok(Self::NoOne),
+            "parent" => Ok(Self::Parent),
+            _        => Err(ArgError::UnknownRequiredBy(s.to_owned()))
+        }
+    }
+}
+
+impl TryFrom<&str> for ArgRequiredBy {
+    type Error = ArgError;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 #[derive(thiserror::Error, Clone, Debug)]
 pub enum ArgError {
     #[error("{0} requires an argument")]
     Missing(String),
+    /// Each reason is paired with the name of
+    /// the validator which failed, or its index
+    /// when registered without a name.
     #[error("{0} invalid with reason(s): {1:?}")]
-    NotValid(String, Vec<String>)
+    NotValid(String, Vec<(String, String)>),
+    #[error("pattern {0:?} invalid: {1}")]
+    NotValidPattern(String, String),
+    #[error("{0:?} is not a known argument requirement")]
+    UnknownRequiredBy(String),
 }
 
 #[derive(thiserror::Error, Clone, Debug)]
 pub enum ResourceError {
     #[error("existing {1} node of {0} already set")]
     AlreadySet(String, String),
+    #[error("path {0} invalid with reason(s): {1:?}")]
+    NotValid(String, Vec<String>),
+}
+
+type ArgValidatorFn<T> = dyn Fn(&T) -> Result<()> + Send + Sync;
+
+/// Validates an argument, failing with the
+/// reason it is not valid.
+#[derive(Clone)]
+enum ArgValidator<T> {
+    Closure(Arc<ArgValidatorFn<T>>),
+    Fn(fn(&T) -> Result<()>),
+}
+
+impl<T> ArgValidator<T> {
+    fn validate(&self, arg: &T) -> Result<()> {
+        match self {
+            Self::Closure(f) => f(arg),
+            Self::Fn(f)      => f(arg)
+        }
+    }
+}
+
+impl<T> Debug for ArgValidator<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ArgValidator")
+    }
 }
 
 /// Represents a single part of of a URI path.
@@ -54,13 +164,19 @@ pub enum ResourceError {
 /// component, or entities that are related to it.
 #[derive(Debug)]
 pub struct ApiResource<'a, T: Display> {
-    name:            &'a str,
-    arg:             Option<T>,
-    arg_required_by: ArgRequiredBy,
-    arg_validators:  Vec<fn(&T) -> Result<()>>,
-    child:           Option<Box<Self>>,
-    parent:          Option<Box<Self>>,
-    weight:          f32,
+    name:                   Cow<'a, str>,
+    arg:                    Option<T>,
+    arg_formatter:          Option<fn(&T) -> String>,
+    arg_only:               bool,
+    arg_required_by:        ArgRequiredBy,
+    arg_required_inherited: Option<ArgRequiredBy>,
+    arg_validators:         Vec<(Option<String>, ArgValidator<T>)>,
+    child:                  Option<Box<Self>>,
+    meta:                   HashMap<String, String>,
+    parent:                 Option<Box<Self>>,
+    path_validators:        Vec<fn(&str) -> Result<()>>,
+    weight:                 f32,
+    weight_offset:          Option<f32>,
 }
 
 /// Barebones basic implementation of an
@@ -73,31 +189,195 @@ impl<'a, T: Display> ApiResource<'a, T> {
     /// Create a new instance of `ApiResource`.
     pub fn new<'b: 'a>(name: &'b str) -> Self {
         Self{
-            name,
+            name: Cow::Borrowed(name),
             arg: None,
+            arg_formatter: None,
+            arg_only: false,
             arg_required_by: ArgRequiredBy::NoOne,
+            arg_required_inherited: None,
             arg_validators: vec![],
             child: None,
+            meta: HashMap::new(),
             parent: None,
-            weight: 0.0
+            path_validators: vec![],
+            weight: 0.0,
+            weight_offset: None
+        }
+    }
+
+    /// Create a new instance of `ApiResource`
+    /// which owns its name.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, CoreResource, PathComponent};
+    /// let resources: Vec<_> = (0..2)
+    ///     .map(|i| ApiResource::<String>::new_owned(format!("resource{i}")))
+    ///     .collect();
+    /// assert_eq!(resources[1].name(), "resource1");
+    /// assert_eq!(resources[1].compose().unwrap(), "resource1/")
+    /// ```
+    pub fn new_owned(name: String) -> ApiResource<'static, T> {
+        ApiResource{name: Cow::Owned(name), ..ApiResource::new("")}
+    }
+
+    /// Registers a validator, named `pattern`,
+    /// which requires the argument, as a string,
+    /// to match the regular expression. The
+    /// pattern is compiled once, failing here if
+    /// it is not valid.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, PathComponent};
+    /// let mut resource = ApiResource::<String>::new("posts");
+    /// resource.with_arg_pattern("^[a-z0-9]+(?:-[a-z0-9]+)*$").unwrap();
+    ///
+    /// resource.with_arg("my-post".into());
+    /// assert_eq!(resource.as_path_component().unwrap(), "posts/my-post");
+    ///
+    /// resource.with_arg("My Post".into());
+    /// assert!(resource.as_path_component().is_err());
+    ///
+    /// resource.with_arg("a".repeat(200_000));
+    /// assert!(resource.as_path_component().is_ok());
+    ///
+    /// assert!(resource.with_arg_pattern("[a-z").is_err())
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn with_arg_pattern(&mut self, pattern: &str) -> Result<&mut Self>
+    where
+        T: Send + Sync + 'static,
+    {
+        let compiled = regex::Regex::new(pattern)
+            .map_err(|e| ArgError::NotValidPattern(pattern.to_owned(), e.to_string()))?;
+        let pattern = pattern.to_owned();
+        let validator = move |arg: &T| match compiled.is_match(&arg.to_string()) {
+            true  => Ok(()),
+            false => Err(anyhow!("{arg} does not match {pattern:?}"))
+        };
+        self.arg_validators.push((Some("pattern".into()), ArgValidator::Closure(Arc::new(validator))));
+        Ok(self)
+    }
+
+    /// Applies an inherited requirement to this
+    /// node and each of its descendants.
+    fn inherit_required(&mut self, required: Option<ArgRequiredBy>) {
+        let mut curr = Some(self);
+        while let Some(node) = curr {
+            if let Some(required) = required {
+                node.arg_required_by = required;
+                node.arg_required_inherited = Some(required);
+            }
+            curr = node.child.as_deref_mut();
         }
     }
+
+    /// Applies relative weights to this node and
+    /// each of its descendants, from the weight
+    /// of the parent.
+    fn inherit_weight(&mut self, weight: f32) {
+        let mut curr   = Some(self);
+        let mut weight = weight;
+        while let Some(node) = curr {
+            if let Some(offset) = node.weight_offset {
+                node.weight = weight + offset;
+            }
+            weight = node.weight;
+            curr   = node.child.as_deref_mut();
+        }
+    }
+
+    /// Renders the argument as it appears in the
+    /// composed path.
+    fn render_arg(&self) -> Option<String> {
+        self.arg.as_ref().map(|a| match self.arg_formatter {
+            Some(f) => (f)(a),
+            None    => a.to_string()
+        })
+        .map(|a| encode_path_segment(&a))
+    }
 }
 
 impl<T: Clone + Display> Clone for ApiResource<'_, T> {
     fn clone(&self) -> Self {
         Self{
-            name: self.name,
+            name: self.name.clone(),
             arg:  self.arg.clone(),
+            arg_formatter: self.arg_formatter,
+            arg_only: self.arg_only,
             arg_required_by: self.arg_required_by,
+            arg_required_inherited: self.arg_required_inherited,
             arg_validators: self.arg_validators.clone(),
             child: self.child.clone(),
+            meta: self.meta.clone(),
             parent: self.parent.clone(),
-            weight: self.weight
+            path_validators: self.path_validators.clone(),
+            weight: self.weight,
+            weight_offset: self.weight_offset
         }
     }
 }
 
+/// Resources are equal when their name,
+/// argument, requirement, weight and children
+/// are equal. Argument validators are not
+/// comparable and are excluded.
+/// ```rust
+/// use uri_resources::{ApiResource, ArgedResource, LinkedResource};
+/// let mut child0 = ApiResource::<String>::new("child_resource");
+/// let mut child1 = ApiResource::<String>::new("child_resource");
+/// let parent0 = ApiResource::<String>::new("parent_resource")
+///     .with_child(&mut child0)
+///     .expect("parent node");
+/// let parent1 = ApiResource::<String>::new("parent_resource")
+///     .with_child(&mut child1)
+///     .expect("parent node");
+/// assert_eq!(parent0, parent1);
+///
+/// let mut child2 = ApiResource::<String>::new("child_resource");
+/// child2.with_arg("arg".into());
+/// let parent2 = ApiResource::<String>::new("parent_resource")
+///     .with_child(&mut child2)
+///     .expect("parent node");
+/// assert_ne!(parent0, parent2);
+/// ```
+impl<T: PartialEq + Display> PartialEq for ApiResource<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.arg == other.arg
+            && self.arg_required_by == other.arg_required_by
+            && OrderedFloat(self.weight) == OrderedFloat(other.weight)
+            && self.child == other.child
+    }
+}
+
+impl<T: Eq + Display> Eq for ApiResource<'_, T> {}
+
+/// Resources are ordered by their weight alone,
+/// ignoring their name and argument.
+/// ```rust
+/// use uri_resources::{ApiResource, CoreResource, WeightedResource};
+/// let mut resources = vec![
+///     ApiResource::<String>::new("users").weighted(2.0),
+///     ApiResource::<String>::new("api").weighted(1.0),
+///     ApiResource::<String>::new("list").weighted(3.0),
+/// ];
+/// resources.sort();
+///
+/// let names: Vec<_> = resources.iter().map(|r| r.name()).collect();
+/// assert_eq!(names, vec!["api", "users", "list"])
+/// ```
+impl<T: Eq + Display> Ord for ApiResource<'_, T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        OrderedFloat(self.weight).cmp(&OrderedFloat(other.weight))
+    }
+}
+
+impl<T: Eq + Display> PartialOrd for ApiResource<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// Composes an object into a path component,
 /// conditionally failing if the implemented
 /// instance does not meet the requirements set
@@ -120,6 +400,17 @@ pub trait PathComponent {
     /// let path = ApiResource::<String>::new("resource").as_path_component();
     /// assert_eq!(path.unwrap(), String::from("resource/"))
     /// ```
+    ///
+    /// Arguments are percent-encoded as path
+    /// segments, the same as `with_path` does.
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, PathComponent};
+    /// use uri_routes::encode_path_segment;
+    /// let path = ApiResource::<String>::new("resource")
+    ///     .with_arg("a b/c:d@e+f".into())
+    ///     .as_path_component();
+    /// assert_eq!(path.unwrap(), format!("resource/{}", encode_path_segment("a b/c:d@e+f")));
+    /// ```
     fn as_path_component(&self) -> Result<String>;
     /// Compose the entire heirarchy of components
     /// into one string.
@@ -156,6 +447,254 @@ pub trait PathComponent {
     /// assert_eq!(path.expect("composed path"), "parent_resource/child_resource0/child_resource1/")
     /// ```
     fn compose(&self) -> Result<String>;
+    /// Compose the entire heirarchy of components
+    /// onto the end of an existing string. The
+    /// appended path is the same as `compose`,
+    /// and nothing is appended on failure.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, LinkedResource, PathComponent};
+    /// let mut child0 = ApiResource::<String>::new("a");
+    /// let parent0 = ApiResource::<String>::new("users")
+    ///     .with_child(&mut child0)
+    ///     .expect("parent node");
+    /// let mut child1 = ApiResource::<String>::new("b");
+    /// let parent1 = ApiResource::<String>::new("posts")
+    ///     .with_child(&mut child1)
+    ///     .expect("parent node");
+    ///
+    /// let mut buf = String::from("/");
+    /// parent0.compose_into(&mut buf).expect("composed path");
+    /// parent1.compose_into(&mut buf).expect("composed path");
+    /// assert_eq!(buf, format!("/{}{}", parent0.compose().unwrap(), parent1.compose().unwrap()));
+    /// assert_eq!(buf, "/users/a/posts/b/")
+    /// ```
+    fn compose_into(&self, buf: &mut String) -> Result<()>;
+    /// Compose each component, from this node to
+    /// the tail, without the separators between
+    /// them. Empty components are skipped.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, LinkedResource, PathComponent};
+    /// let mut child0 = ApiResource::<String>::new("child_resource0");
+    /// let mut child1 = ApiResource::<String>::new("child_resource1");
+    /// child1.with_arg("42".into());
+    ///
+    /// child0 = *child0.with_child(&mut child1).expect("resource node");
+    /// let parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child0)
+    ///     .expect("parent node");
+    ///
+    /// assert_eq!(parent.compose_segments().expect("composed segments"), vec![
+    ///     "parent_resource",
+    ///     "child_resource0",
+    ///     "child_resource1/42",
+    /// ])
+    /// ```
+    ///
+    /// Errors surface as they do from `compose`,
+    /// including those of the chain.
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, ArgRequiredBy, LinkedResource, PathComponent};
+    /// let mut child = ApiResource::<String>::new("child");
+    /// let mut parent = ApiResource::<String>::new("parent");
+    /// parent.with_arg_required(ArgRequiredBy::All);
+    /// let parent = parent.with_child(&mut child).expect("parent node");
+    ///
+    /// assert!(parent.compose().is_err());
+    /// assert!(parent.compose_segments().is_err());
+    /// assert!(parent.into_route_plan().is_err())
+    /// ```
+    fn compose_segments(&self) -> Result<Vec<String>>;
+    /// Compose the entire heirarchy of components
+    /// into a path template, where nodes which
+    /// require an argument are followed by a
+    /// `{name}` placeholder, whether or not an
+    /// argument is set.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, ArgRequiredBy, LinkedResource, PathComponent};
+    /// let mut child = ApiResource::<String>::new("posts");
+    /// let mut parent = ApiResource::<String>::new("users");
+    /// parent.with_arg_required(ArgRequiredBy::Me).with_arg("42".into());
+    /// let parent = parent.with_child(&mut child).expect("parent node");
+    ///
+    /// assert_eq!(parent.compose_template(), "/users/{users}/posts")
+    /// ```
+    fn compose_template(&self) -> String;
+    /// Compose each component, from this node to
+    /// the tail, paired with the weight of its
+    /// node, ready to be added to a route.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, LinkedResource, PathComponent, WeightedResource};
+    /// let mut child = ApiResource::<String>::new("users");
+    /// child.with_arg("42".into()).with_weight(2.5);
+    /// let mut parent = ApiResource::<String>::new("api");
+    /// parent.with_weight(1.0);
+    /// let parent = parent.with_child(&mut child).expect("parent node");
+    ///
+    /// assert_eq!(parent.into_route_plan().expect("route plan"), vec![
+    ///     ("api".into(), 1.0),
+    ///     ("users/42".into(), 2.5),
+    /// ])
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn into_route_plan(&self) -> Result<Vec<(String, f32)>>;
+    /// Compose the entire heirarchy of components
+    /// into one string, without the trailing
+    /// separator.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, LinkedResource, PathComponent};
+    /// let mut child0 = ApiResource::<String>::new("child_resource0");
+    /// let mut child1 = ApiResource::<String>::new("child_resource1");
+    ///
+    /// child0 = *child0.with_child(&mut child1).expect("resource node");
+    /// let parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child0)
+    ///     .expect("parent node");
+    ///
+    /// assert_eq!(parent.compose().expect("composed path"), "parent_resource/child_resource0/child_resource1/");
+    /// assert_eq!(parent.compose_trimmed().expect("composed path"), "parent_resource/child_resource0/child_resource1")
+    /// ```
+    fn compose_trimmed(&self) -> Result<String>;
+    /// Compose the components from this node to
+    /// the tail, ignoring any ancestors, into a
+    /// relative path.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, LinkedResource, PathComponent};
+    /// let mut child0 = ApiResource::<String>::new("child_resource0");
+    /// let mut child1 = ApiResource::<String>::new("child_resource1");
+    ///
+    /// child0 = *child0.with_child(&mut child1).expect("resource node");
+    /// let parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child0)
+    ///     .expect("parent node");
+    ///
+    /// let middle = parent.child().expect("middle node");
+    /// assert_eq!(middle.compose_from().expect("composed path"), "child_resource0/child_resource1/")
+    /// ```
+    fn compose_from(&self) -> Result<String>;
+    /// Compose the entire heirarchy of components
+    /// into the path of a URI on the given host.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, LinkedResource, PathComponent};
+    /// let mut child = ApiResource::<String>::new("b");
+    /// let parent = ApiResource::<String>::new("a")
+    ///     .with_child(&mut child)
+    ///     .expect("parent node");
+    ///
+    /// let uri = parent.to_uri("fqdm.org").expect("built uri");
+    /// assert_eq!(uri, "https://fqdm.org/a/b/")
+    /// ```
+    fn to_uri(&self, host: &str) -> Result<http::Uri>;
+    /// Registers a validator run against the
+    /// entire path composed from this node.
+    /// Failures of every validator are reported
+    /// together.
+    ///
+    /// ```rust
+    /// use anyhow::anyhow;
+    /// use uri_resources::{ApiResource, LinkedResource, PathComponent};
+    /// let mut child = ApiResource::<String>::new("child_resource");
+    /// let mut parent = ApiResource::<String>::new("parent_resource");
+    /// parent.with_path_validator(|p| match p.len() {
+    ///     0..=16 => Ok(()),
+    ///     n => Err(anyhow!("path length {n} exceeds 16"))
+    /// });
+    /// assert!(parent.compose().is_ok());
+    ///
+    /// let parent = parent.with_child(&mut child).expect("parent node");
+    /// assert!(parent.compose().is_err())
+    /// ```
+    fn with_path_validator(&mut self, f: fn(&str) -> Result<()>) -> &mut Self;
+    /// Collects the names of every node, from
+    /// this one to the tail, which require an
+    /// argument that has not been set.
+    ///
+    /// Nodes that are required by no one never
+    /// appear, whether or not they have an
+    /// argument.
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, ArgRequiredBy, LinkedResource, PathComponent};
+    /// let mut child0 = ApiResource::<String>::new("child_resource0");
+    /// let mut child1 = ApiResource::<String>::new("child_resource1");
+    /// child0.with_arg_required(ArgRequiredBy::Me);
+    /// child1.with_arg_required(ArgRequiredBy::Parent);
+    ///
+    /// child0 = *child0.with_child(&mut child1).expect("resource node");
+    /// let parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child0)
+    ///     .expect("parent node");
+    ///
+    /// assert_eq!(parent.missing_args(), vec!["child_resource0", "child_resource1"])
+    /// ```
+    fn missing_args(&self) -> Vec<String>;
+    /// Each node's name, from this one to the
+    /// tail, paired with its argument as it is
+    /// rendered in the composed path.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, LinkedResource, PathComponent};
+    /// let mut child = ApiResource::<String>::new("child_resource");
+    /// child.with_arg("a b".into());
+    /// let parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child)
+    ///     .expect("parent node");
+    ///
+    /// assert_eq!(parent.bindings(), vec![
+    ///     ("parent_resource".into(), None),
+    ///     ("child_resource".into(), Some("a%20b".into())),
+    /// ])
+    /// ```
+    fn bindings(&self) -> Vec<(String, Option<String>)>;
+    /// Whether every node, from this one to the
+    /// tail, which requires an argument has one
+    /// set. Argument and path validators are not
+    /// run, so `compose` may still fail on them.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, ArgRequiredBy, LinkedResource, PathComponent};
+    /// let mut child = ApiResource::<String>::new("child_resource");
+    /// child.with_arg_required(ArgRequiredBy::Me);
+    /// let mut parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child)
+    ///     .expect("parent node");
+    ///
+    /// assert!(!parent.is_complete());
+    /// assert!(parent.compose().is_err());
+    ///
+    /// parent.child_mut().unwrap().with_arg("1".into());
+    /// assert!(parent.is_complete());
+    /// assert!(parent.compose().is_ok())
+    /// ```
+    fn is_complete(&self) -> bool;
+    /// Checks every node, from this one to the
+    /// tail, for missing or invalid arguments,
+    /// collecting every failure rather than
+    /// stopping at the first.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, ArgError, ArgRequiredBy, LinkedResource, PathComponent};
+    /// let mut child0 = ApiResource::<String>::new("child_resource0");
+    /// let mut child1 = ApiResource::<String>::new("child_resource1");
+    /// child0.with_arg_required(ArgRequiredBy::Me);
+    /// child1.with_parsed_validator::<u32>().with_arg("abc".into());
+    ///
+    /// child0 = *child0.with_child(&mut child1).expect("resource node");
+    /// let parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child0)
+    ///     .expect("parent node");
+    ///
+    /// let errors = parent.validate_all().unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert!(matches!(&errors[0], ArgError::Missing(n) if n == "child_resource0"));
+    /// assert!(matches!(&errors[1], ArgError::NotValid(n, _) if n == "child_resource1"))
+    /// ```
+    fn validate_all(&self) -> std::result::Result<(), Vec<ArgError>>;
 }
 
 impl<'a, T: Debug + Display + Clone> PathComponent for ApiResource<'a, T> {
@@ -165,20 +704,16 @@ impl<'a, T: Debug + Display + Clone> PathComponent for ApiResource<'a, T> {
         };
 
         let compose_this = || {
-            let errors: Vec<_> = self.arg_validators
-                .iter()
-                .map(|f| { (f)(self.arg.as_ref().unwrap()) })
-                .filter(|r| r.is_err())
-                .map(|r| r.unwrap_err().to_string())
-                .collect();
-
+            let errors = self.validate_arg();
             if !errors.is_empty()  {
                 Err(ArgError::NotValid(self.name(), errors).into())
+            } else if self.arg_only {
+                Ok(self.render_arg().unwrap_or_default())
             } else {
                 let ret = format!(
                     "{}/{}",
                     self.name(),
-                    self.arg.clone().map_or("".into(), |a| a.to_string()));
+                    self.render_arg().unwrap_or_default());
                 Ok(ret)
             }
         };
@@ -189,25 +724,240 @@ impl<'a, T: Debug + Display + Clone> PathComponent for ApiResource<'a, T> {
             to_argnotfound(self.parent().unwrap())
         } else if self.required_by().is_child() && self.child.is_some() {
             to_argnotfound(self.child().unwrap())
+        } else if self.required_by().is_me() {
+            to_argnotfound(self)
         } else {
             compose_this()
         }
     }
 
     fn compose(&self) -> Result<String> {
+        let mut path = String::new();
+        self.compose_into(&mut path)?;
+        Ok(path)
+    }
+
+    fn compose_into(&self, buf: &mut String) -> Result<()> {
+        self.check_chain_args()?;
+
+        let start    = buf.len();
         let mut curr = Some(self);
-        let mut components = vec![];
+        let mut sep  = "";
 
-        while curr.is_some() {
-            components.push(match curr.unwrap().as_path_component() {
+        while let Some(node) = curr {
+            match node.as_path_component() {
                 Ok(path) => {
-                    curr = curr.unwrap().child();
-                    path
+                    buf.push_str(sep);
+                    buf.push_str(&path);
+                    sep  = "/";
+                    curr = node.child();
                 },
-                e => return e
-            });
+                Err(e) => {
+                    buf.truncate(start);
+                    return Err(e)
+                }
+            }
+        }
+        if buf[start..].contains("//") {
+            let path = buf[start..].replace("//", "/");
+            buf.truncate(start);
+            buf.push_str(&path);
+        }
+
+        let errors: Vec<_> = self.path_validators
+            .iter()
+            .filter_map(|f| (f)(&buf[start..]).err())
+            .map(|e| e.to_string())
+            .collect();
+
+        if !errors.is_empty() {
+            let path = buf.split_off(start);
+            Err(ResourceError::NotValid(path, errors).into())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn compose_from(&self) -> Result<String> {
+        self.compose()
+    }
+
+    fn compose_segments(&self) -> Result<Vec<String>> {
+        self.check_chain_args()?;
+        let mut curr = Some(self);
+        let mut segments = vec![];
+
+        while let Some(node) = curr {
+            let segment = node.as_path_component()?;
+            let segment = segment.trim_matches('/');
+            if !segment.is_empty() {
+                segments.push(segment.to_owned());
+            }
+            curr = node.child();
+        }
+        Ok(segments)
+    }
+
+    fn into_route_plan(&self) -> Result<Vec<(String, f32)>> {
+        self.check_chain_args()?;
+        let mut curr = Some(self);
+        let mut plan = vec![];
+
+        while let Some(node) = curr {
+            let segment = node.as_path_component()?;
+            let segment = segment.trim_matches('/');
+            if !segment.is_empty() {
+                plan.push((segment.to_owned(), node.weight));
+            }
+            curr = node.child();
+        }
+        Ok(plan)
+    }
+
+    fn compose_template(&self) -> String {
+        let mut curr = Some(self);
+        let mut template = String::new();
+
+        while let Some(node) = curr {
+            let placeholder = format!("{{{}}}", node.name);
+            match (node.arg_only, node.required_by().is_noone()) {
+                (true, _)      => template.push_str(&format!("/{placeholder}")),
+                (false, true)  => template.push_str(&format!("/{}", node.name)),
+                (false, false) => template.push_str(&format!("/{}/{placeholder}", node.name))
+            }
+            curr = node.child();
+        }
+        template
+    }
+
+    fn compose_trimmed(&self) -> Result<String> {
+        self.compose().map(|p| p.trim_end_matches('/').to_owned())
+    }
+
+    fn to_uri(&self, host: &str) -> Result<http::Uri> {
+        let path = self.compose()?;
+        Ok(ApiRouteBuilder::new(host)
+            .with_path_raw(&path)
+            .build()?)
+    }
+
+    fn with_path_validator(&mut self, f: fn(&str) -> Result<()>) -> &mut Self {
+        self.path_validators.push(f);
+        self
+    }
+
+    fn missing_args(&self) -> Vec<String> {
+        self.chain_missing_args()
+            .into_iter()
+            .filter(|(_, missing)| *missing)
+            .map(|(node, _)| node.name())
+            .collect()
+    }
+
+    fn bindings(&self) -> Vec<(String, Option<String>)> {
+        let mut curr = Some(self);
+        let mut bindings = vec![];
+
+        while let Some(node) = curr {
+            bindings.push((node.name(), node.render_arg()));
+            curr = node.child();
+        }
+        bindings
+    }
+
+    fn is_complete(&self) -> bool {
+        self.chain_missing_args()
+            .into_iter()
+            .all(|(_, missing)| !missing)
+    }
+
+    fn validate_all(&self) -> std::result::Result<(), Vec<ArgError>> {
+        let mut errors = vec![];
+
+        for (node, missing) in self.chain_missing_args() {
+            if missing {
+                errors.push(ArgError::Missing(node.name()));
+            }
+            let reasons = node.validate_arg();
+            if !reasons.is_empty() {
+                errors.push(ArgError::NotValid(node.name(), reasons));
+            }
+        }
+
+        match errors.is_empty() {
+            true  => Ok(()),
+            false => Err(errors)
+        }
+    }
+}
+
+impl<'a, T: Debug + Display + Clone> ApiResource<'a, T> {
+    /// Each node, from this one to the tail,
+    /// paired with whether it is missing an
+    /// argument, including by the `All` and
+    /// `Any` requirements of the chain.
+    fn chain_missing_args(&self) -> Vec<(&Self, bool)> {
+        let mut curr = Some(self);
+        let mut nodes = vec![];
+
+        while let Some(node) = curr {
+            nodes.push(node);
+            curr = node.child();
+        }
+
+        let all = nodes.iter().any(|n| n.required_by().is_all());
+        let any = nodes.iter().any(|n| n.required_by().is_any())
+            && nodes.iter().all(|n| n.arg.is_none());
+
+        nodes
+            .into_iter()
+            .map(|n| {
+                let missing = n.is_missing_arg()
+                    || (all && n.arg.is_none())
+                    || (any && n.required_by().is_any());
+                (n, missing)
+            })
+            .collect()
+    }
+
+    /// Fails on the first node missing an
+    /// argument by the `All` or `Any`
+    /// requirements of the chain, which are not
+    /// checked node by node.
+    fn check_chain_args(&self) -> Result<()> {
+        let unmet = self.chain_missing_args()
+            .into_iter()
+            .find(|(node, missing)| *missing && !node.is_missing_arg());
+        match unmet {
+            Some((node, _)) => Err(ArgError::Missing(node.name()).into()),
+            None            => Ok(())
+        }
+    }
+
+    /// Runs each argument validator, collecting
+    /// the name and reason of those which fail.
+    fn validate_arg(&self) -> Vec<(String, String)> {
+        self.arg
+            .iter()
+            .flat_map(|a| self.arg_validators.iter().enumerate().map(move |(i, (n, f))| {
+                let name = n.clone().unwrap_or(i.to_string());
+                f.validate(a).map_err(|e| (name, e.to_string()))
+            }))
+            .filter_map(|r| r.err())
+            .collect()
+    }
+
+    /// Whether this node requires an argument
+    /// which has not yet been set.
+    fn is_missing_arg(&self) -> bool {
+        self.arg.is_none() && match self.required_by() {
+            ArgRequiredBy::All    => false,
+            ArgRequiredBy::Any    => false,
+            ArgRequiredBy::Child  => self.child.is_some(),
+            ArgRequiredBy::Me     => true,
+            ArgRequiredBy::NoOne  => false,
+            ArgRequiredBy::Parent => self.parent.is_some(),
         }
-        Ok(components.join("/").replace("//", "/"))
     }
 }
 
@@ -223,6 +973,194 @@ pub trait ArgedResource<T> {
     /// Sets if, and by whom, this component's
     /// argument is required.
     fn with_arg_required(&mut self, required: ArgRequiredBy) -> &mut Self;
+    /// Sets an argument on this resource if one
+    /// is given, otherwise leaves it unchanged.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource};
+    /// let mut resource = ApiResource::<String>::new("filter");
+    /// resource.with_arg_opt(None);
+    /// assert_eq!(resource.argument(), None);
+    ///
+    /// resource.with_arg_opt(Some("active".into()));
+    /// assert_eq!(resource.argument(), Some(&"active".into()))
+    /// ```
+    fn with_arg_opt(&mut self, arg: Option<T>) -> &mut Self;
+    /// Sets an argument on this resource from
+    /// any value convertible into one.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource};
+    /// let mut resource = ApiResource::<String>::new("user");
+    /// resource.with_arg_into("42");
+    /// assert_eq!(resource.argument(), Some(&String::from("42")))
+    /// ```
+    fn with_arg_into<U: Into<T>>(&mut self, arg: U) -> &mut Self;
+    /// Compose the entire heirarchy of components,
+    /// taking the argument of each node by its
+    /// name from `args`, or the node's own
+    /// argument when not given.
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use uri_resources::{ApiResource, ArgedResource, ArgRequiredBy, LinkedResource, PathComponent};
+    /// let mut child = ApiResource::<String>::new("posts");
+    /// child.with_arg_required(ArgRequiredBy::Me);
+    /// let mut parent = ApiResource::<String>::new("users");
+    /// parent.with_arg_required(ArgRequiredBy::Me);
+    /// let parent = parent.with_child(&mut child).expect("parent node");
+    ///
+    /// let args = HashMap::from([("users", "42".to_owned()), ("posts", "7".to_owned())]);
+    /// assert_eq!(parent.compose_with(&args).expect("composed path"), "users/42/posts/7");
+    /// assert!(parent.compose_with(&HashMap::from([("users", "42".to_owned())])).is_err());
+    /// assert!(parent.compose().is_err())
+    /// ```
+    fn compose_with(&self, args: &HashMap<&str, T>) -> Result<String>
+    where
+        T: Debug;
+    /// Sets if, and by whom, this component's
+    /// argument is required, along with that of
+    /// every descendant. Descendants linked
+    /// afterwards inherit the requirement when
+    /// they are linked, replacing their own.
+    /// Setting a requirement on a descendant
+    /// after it has been linked overrides the
+    /// inherited requirement.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, ArgRequiredBy, LinkedResource, PathComponent};
+    /// let mut child0 = ApiResource::<String>::new("child_resource0");
+    /// let mut child1 = ApiResource::<String>::new("child_resource1");
+    /// child0 = *child0.with_child(&mut child1).expect("resource node");
+    ///
+    /// let mut parent = ApiResource::<String>::new("parent_resource");
+    /// parent.with_arg_required_recursive(ArgRequiredBy::Me);
+    /// let parent = parent.with_child(&mut child0).expect("parent node");
+    ///
+    /// assert!(parent.compose().is_err());
+    /// assert_eq!(parent.missing_args(), vec!["parent_resource", "child_resource0", "child_resource1"])
+    /// ```
+    fn with_arg_required_recursive(&mut self, required: ArgRequiredBy) -> &mut Self;
+    /// Registers a validator which requires the
+    /// argument to be parsable as `P`.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, PathComponent};
+    /// let mut resource = ApiResource::<String>::new("page");
+    /// resource.with_parsed_validator::<u32>();
+    ///
+    /// resource.with_arg("abc".into());
+    /// assert!(resource.as_path_component().is_err());
+    ///
+    /// resource.with_arg("42".into());
+    /// assert_eq!(resource.as_path_component().unwrap(), "page/42")
+    /// ```
+    fn with_parsed_validator<P: FromStr>(&mut self) -> &mut Self
+    where
+        T: AsRef<str>;
+    /// Registers a validator which, when it
+    /// fails, is reported by the given name.
+    ///
+    /// ```rust
+    /// use anyhow::{anyhow, Result};
+    /// use uri_resources::{ApiResource, ArgedResource, ArgError, PathComponent};
+    ///
+    /// fn short(arg: &String) -> Result<()> {
+    ///     match arg.len() < 4 {
+    ///         true  => Ok(()),
+    ///         false => Err(anyhow!("too long")),
+    ///     }
+    /// }
+    ///
+    /// fn lower(arg: &String) -> Result<()> {
+    ///     match arg.chars().all(|c| c.is_lowercase()) {
+    ///         true  => Ok(()),
+    ///         false => Err(anyhow!("not lowercase")),
+    ///     }
+    /// }
+    ///
+    /// let mut resource = ApiResource::<String>::new("tag");
+    /// resource
+    ///     .with_validator_named("short", short)
+    ///     .with_validator_named("lower", lower)
+    ///     .with_arg("ABCDE".into());
+    ///
+    /// let error = resource.as_path_component().unwrap_err();
+    /// match error.downcast_ref::<ArgError>() {
+    ///     Some(ArgError::NotValid(name, reasons)) => {
+    ///         assert_eq!(name, "tag");
+    ///         assert_eq!(reasons, &vec![
+    ///             ("short".to_owned(), "too long".to_owned()),
+    ///             ("lower".to_owned(), "not lowercase".to_owned()),
+    ///         ]);
+    ///     },
+    ///     _ => panic!("expected invalid argument")
+    /// }
+    /// ```
+    fn with_validator_named(&mut self, name: &str, f: fn(&T) -> Result<()>) -> &mut Self;
+    /// Registers a validator, named `range`,
+    /// which requires the argument to be between
+    /// `min` and `max`, inclusive.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, ArgError, PathComponent};
+    /// let mut resource = ApiResource::<u32>::new("page");
+    /// resource.with_arg_range(1, 100);
+    ///
+    /// resource.with_arg(0);
+    /// let error = resource.as_path_component().unwrap_err();
+    /// assert!(matches!(error.downcast_ref::<ArgError>(), Some(ArgError::NotValid(..))));
+    /// assert_eq!(error.to_string(), r#"page invalid with reason(s): [("range", "0 is not between 1 and 100")]"#);
+    ///
+    /// resource.with_arg(101);
+    /// assert!(resource.as_path_component().is_err());
+    ///
+    /// resource.with_arg(50);
+    /// assert_eq!(resource.as_path_component().unwrap(), "page/50")
+    /// ```
+    fn with_arg_range(&mut self, min: T, max: T) -> &mut Self
+    where
+        T: PartialOrd + Send + Sync + 'static;
+    /// Overrides how the argument is rendered in
+    /// the path, in place of `Display`.
+    ///
+    /// ```rust
+    /// use std::fmt::{self, Display};
+    /// use uri_resources::{ApiResource, ArgedResource, PathComponent};
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct Id(&'static str);
+    ///
+    /// impl Display for Id {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "{}", self.0.to_uppercase())
+    ///     }
+    /// }
+    ///
+    /// let mut resource = ApiResource::<Id>::new("item");
+    /// resource
+    ///     .with_arg(Id("AbC-123"))
+    ///     .with_arg_formatter(|id| id.to_string().to_lowercase());
+    /// assert_eq!(resource.as_path_component().unwrap(), "item/abc-123")
+    /// ```
+    fn with_arg_formatter(&mut self, f: fn(&T) -> String) -> &mut Self;
+    /// Makes this component a path parameter,
+    /// emitting only its argument, or nothing at
+    /// all when the argument is absent.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, LinkedResource, PathComponent};
+    /// let mut id = ApiResource::<String>::new("id");
+    /// id.as_arg_segment();
+    /// let mut parent = ApiResource::<String>::new("users")
+    ///     .with_child(&mut id)
+    ///     .expect("parent node");
+    /// assert_eq!(parent.compose().expect("composed path"), "users/");
+    ///
+    /// parent.child_mut().expect("child node").with_arg("42".into());
+    /// assert_eq!(parent.compose().expect("composed path"), "users/42")
+    /// ```
+    fn as_arg_segment(&mut self) -> &mut Self;
 }
 
 impl<'a, T: Clone + Display> ArgedResource<T> for ApiResource<'a, T> {
@@ -243,6 +1181,85 @@ impl<'a, T: Clone + Display> ArgedResource<T> for ApiResource<'a, T> {
         self.arg_required_by = required;
         self
     }
+
+    fn with_arg_opt(&mut self, arg: Option<T>) -> &mut Self {
+        if arg.is_some() {
+            self.arg = arg;
+        }
+        self
+    }
+
+    fn with_arg_into<U: Into<T>>(&mut self, arg: U) -> &mut Self {
+        self.with_arg(arg.into())
+    }
+
+    fn compose_with(&self, args: &HashMap<&str, T>) -> Result<String>
+    where
+        T: Debug,
+    {
+        let mut bound = self.clone();
+        let mut curr  = Some(&mut bound);
+
+        while let Some(node) = curr {
+            if let Some(arg) = args.get(node.name.as_ref()) {
+                node.arg = Some(arg.clone());
+            }
+            curr = node.child.as_deref_mut();
+        }
+        bound.compose()
+    }
+
+    fn with_arg_required_recursive(&mut self, required: ArgRequiredBy) -> &mut Self {
+        self.inherit_required(Some(required));
+        self
+    }
+
+    fn with_parsed_validator<P: FromStr>(&mut self) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        self.arg_validators.push((None, ArgValidator::Fn(parsed_validator::<T, P>)));
+        self
+    }
+
+    fn with_validator_named(&mut self, name: &str, f: fn(&T) -> Result<()>) -> &mut Self {
+        self.arg_validators.push((Some(name.to_owned()), ArgValidator::Fn(f)));
+        self
+    }
+
+    fn with_arg_range(&mut self, min: T, max: T) -> &mut Self
+    where
+        T: PartialOrd + Send + Sync + 'static,
+    {
+        let validator = move |arg: &T| match *arg >= min && *arg <= max {
+            true  => Ok(()),
+            false => Err(anyhow!("{arg} is not between {min} and {max}"))
+        };
+        self.arg_validators.push((Some("range".into()), ArgValidator::Closure(Arc::new(validator))));
+        self
+    }
+
+    fn with_arg_formatter(&mut self, f: fn(&T) -> String) -> &mut Self {
+        self.arg_formatter = Some(f);
+        self
+    }
+
+    fn as_arg_segment(&mut self) -> &mut Self {
+        self.arg_only = true;
+        self
+    }
+}
+
+/// Validates that an argument can be parsed
+/// into the type `P`.
+fn parsed_validator<T: AsRef<str>, P: FromStr>(arg: &T) -> Result<()> {
+    match arg.as_ref().parse::<P>() {
+        Ok(_)  => Ok(()),
+        Err(_) => Err(anyhow!(
+            "{:?} cannot be parsed as {}",
+            arg.as_ref(),
+            std::any::type_name::<P>()))
+    }
 }
 
 /// The core functionality that is to be expected
@@ -254,19 +1271,103 @@ pub trait CoreResource<T> {
     /// The name of the resource component. Is
     /// used as the path component on digestion.
     fn name(&self) -> String;
+    /// A snapshot of this resource's
+    /// configuration.
+    /// ```rust
+    /// use uri_resources::{ApiResource, CoreResource, LinkedResource};
+    /// let mut child0 = ApiResource::<String>::new("child_resource0");
+    /// let mut child1 = ApiResource::<String>::new("child_resource1");
+    ///
+    /// child0 = *child0.with_child(&mut child1).expect("resource node");
+    /// let parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child0);
+    ///
+    /// let summary = child0.summary();
+    /// println!("{summary:?}");
+    /// assert!(!summary.is_root && !summary.is_tail)
+    /// ```
+    fn summary(&self) -> ResourceSummary;
+    /// Metadata value set on this resource.
+    fn meta(&self, key: &str) -> Option<&str>;
+    /// Attaches a metadata key/value to this
+    /// resource. Metadata travels with the
+    /// resource but never affects the path.
+    /// ```rust
+    /// use uri_resources::{ApiResource, CoreResource, PathComponent};
+    /// let mut resource = ApiResource::<String>::new("users");
+    /// resource
+    ///     .with_meta("method", "GET".into())
+    ///     .with_meta("scope", "users:read".into());
+    ///
+    /// assert_eq!(resource.meta("method"), Some("GET"));
+    /// assert_eq!(resource.meta("scope"), Some("users:read"));
+    /// assert_eq!(resource.meta("missing"), None);
+    /// assert_eq!(resource.compose().unwrap(), "users/")
+    /// ```
+    fn with_meta(&mut self, key: &str, value: String) -> &mut Self;
 }
 
 impl<'a, T: Clone + Display> CoreResource<T> for ApiResource<'a, T> {
     fn name(&self) -> String {
-        self.name.to_owned()
+        self.name.to_string()
+    }
+
+    fn summary(&self) -> ResourceSummary {
+        ResourceSummary{
+            name: self.name(),
+            has_arg: self.arg.is_some(),
+            required_by: self.arg_required_by,
+            weight: self.weight,
+            is_root: self.parent.is_none(),
+            is_tail: self.child.is_none()
+        }
+    }
+
+    fn meta(&self, key: &str) -> Option<&str> {
+        self.meta.get(key).map(String::as_str)
+    }
+
+    fn with_meta(&mut self, key: &str, value: String) -> &mut Self {
+        self.meta.insert(key.to_owned(), value);
+        self
     }
 }
 
+/// Snapshot of the configuration of a single
+/// resource node.
+#[derive(Clone, Debug)]
+pub struct ResourceSummary {
+    pub name:        String,
+    pub has_arg:     bool,
+    pub required_by: ArgRequiredBy,
+    pub weight:      f32,
+    pub is_root:     bool,
+    pub is_tail:     bool,
+}
+
 /// Allows resources to set their child and parent
 /// nodes.
 pub trait LinkedResource<'a, T: Display> {
     /// The child `Resource` node.
     fn child(&self) -> Option<&Self>;
+    /// The child `Resource` node, mutably.
+    ///
+    /// Linking stores a copy of the child, so
+    /// changes to a node after it has been linked
+    /// must be made through the chain itself.
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, ArgRequiredBy, LinkedResource, PathComponent};
+    /// let mut child = ApiResource::<String>::new("child_resource");
+    /// child.with_arg_required(ArgRequiredBy::Me);
+    /// let mut parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child)
+    ///     .expect("parent node");
+    /// assert!(parent.compose().is_err());
+    ///
+    /// parent.child_mut().expect("child node").with_arg("arg".into());
+    /// assert_eq!(parent.compose().expect("composed path"), "parent_resource/child_resource/arg")
+    /// ```
+    fn child_mut(&mut self) -> Option<&mut Self>;
     /// The parent `Resource` node.
     fn parent(&self) -> Option<&Self>;
     /// If this is a child of another resource.
@@ -358,11 +1459,116 @@ pub trait LinkedResource<'a, T: Display> {
     /// ```
     fn is_tail(&self) -> bool;
     /// Adds a child node to this resource. Fails
-    /// if the child is already set.
+    /// if the child is already set. A copy of the
+    /// child is linked, later changes to `child`
+    /// itself are not reflected in this chain.
     fn with_child(&mut self, child: &mut ApiResource<'a, T>) -> Result<Box<Self>>;
-    /// Adds the parent node to this resource.
-    /// Fails if the parent is already set.
+    /// Adds the parent node to this resource,
+    /// also setting this as the parent's child.
+    /// Fails if either link is already set.
+    ///
+    /// Each side stores a copy of the other, as
+    /// it is at the time of linking.
+    /// ```rust
+    /// use uri_resources::{ApiResource, LinkedResource, PathComponent};
+    /// let mut parent = ApiResource::<String>::new("parent_resource");
+    /// let mut child  = ApiResource::<String>::new("child_resource");
+    /// child.with_parent(&mut parent).expect("child node");
+    ///
+    /// assert!(child.is_child());
+    /// assert_eq!(parent.compose().expect("composed path"), "parent_resource/child_resource/")
+    /// ```
     fn with_parent(&mut self, parent: &mut ApiResource<'a, T>) -> Result<Box<Self>>;
+    /// Takes ownership of a child node, linking
+    /// it to this resource in place. Fails if
+    /// this child, or the child's parent, is
+    /// already set.
+    /// ```rust
+    /// use uri_resources::{ApiResource, LinkedResource, PathComponent};
+    /// let mut child = ApiResource::<String>::new("child_resource0");
+    /// child.attach_child(ApiResource::new("child_resource1")).expect("child node");
+    ///
+    /// let mut parent = ApiResource::<String>::new("parent_resource");
+    /// let path = parent
+    ///     .attach_child(child)
+    ///     .expect("parent node")
+    ///     .compose();
+    /// assert_eq!(path.expect("composed path"), "parent_resource/child_resource0/child_resource1/");
+    /// assert!(parent.child().expect("child node").is_child())
+    /// ```
+    fn attach_child(&mut self, child: ApiResource<'a, T>) -> Result<&mut Self>;
+    /// Removes the child node from this resource,
+    /// returning it without its parent link.
+    /// ```rust
+    /// use uri_resources::{ApiResource, LinkedResource, PathComponent};
+    /// let mut parent = ApiResource::<String>::new("parent_resource");
+    /// parent.attach_child(ApiResource::new("child_resource")).expect("parent node");
+    ///
+    /// let child = parent.detach_child().expect("child node");
+    /// assert!(child.is_root() && parent.is_tail());
+    /// assert_eq!(parent.compose().expect("composed path"), "parent_resource/");
+    /// assert_eq!(child.compose().expect("composed path"), "child_resource/")
+    /// ```
+    fn detach_child(&mut self) -> Option<ApiResource<'a, T>>;
+    /// Finds the first node, from this one to
+    /// the tail, with the given name. Where names
+    /// repeat, the node closest to this one is
+    /// found.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, CoreResource, LinkedResource};
+    /// let mut child0 = ApiResource::<String>::new("child_resource0");
+    /// let mut child1 = ApiResource::<String>::new("child_resource1");
+    ///
+    /// child0 = *child0.with_child(&mut child1).expect("resource node");
+    /// let parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child0)
+    ///     .expect("parent node");
+    ///
+    /// let middle = parent.find("child_resource0").expect("middle node");
+    /// assert_eq!(middle.child().expect("tail node").name(), "child_resource1");
+    /// assert!(parent.find("missing").is_none())
+    /// ```
+    fn find(&self, name: &str) -> Option<&Self>;
+    /// Finds the first node, from this one to
+    /// the tail, with the given name, for
+    /// modification.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, LinkedResource, PathComponent};
+    /// let mut child = ApiResource::<String>::new("child_resource");
+    /// let mut parent = ApiResource::<String>::new("parent_resource")
+    ///     .with_child(&mut child)
+    ///     .expect("parent node");
+    ///
+    /// parent.find_mut("child_resource").expect("child node").with_arg("42".into());
+    /// assert_eq!(parent.compose().expect("composed path"), "parent_resource/child_resource/42")
+    /// ```
+    fn find_mut(&mut self, name: &str) -> Option<&mut Self>;
+    /// Rebuilds the chain, from this node to the
+    /// tail, with parent and child roles swapped.
+    /// The tail becomes the new root, and each
+    /// node keeps its argument, weight and
+    /// validators.
+    ///
+    /// ```rust
+    /// use uri_resources::{ApiResource, ArgedResource, CoreResource, LinkedResource, PathComponent};
+    /// let mut middle = ApiResource::<String>::new("middle");
+    /// middle.with_arg("42".into());
+    /// middle.attach_child(ApiResource::new("tail")).expect("middle node");
+    ///
+    /// let mut root = ApiResource::<String>::new("root");
+    /// root.attach_child(middle).expect("root node");
+    /// assert_eq!(root.compose().expect("composed path"), "root/middle/42/tail/");
+    ///
+    /// let reversed = root.reverse().expect("reversed chain");
+    /// assert_eq!(reversed.name(), "tail");
+    /// assert!(reversed.is_root() && reversed.find("root").expect("tail node").is_tail());
+    /// assert_eq!(reversed.compose().expect("composed path"), "tail/middle/42/root/")
+    /// ```
+    fn reverse(&self) -> Result<Self>
+    where
+        Self: Sized;
 }
 
 impl<'a, T: Debug + Display + Clone> LinkedResource<'a, T> for ApiResource<'a, T> {
@@ -370,6 +1576,10 @@ impl<'a, T: Debug + Display + Clone> LinkedResource<'a, T> for ApiResource<'a, T
         self.child.as_deref()
     }
 
+    fn child_mut(&mut self) -> Option<&mut Self> {
+        self.child.as_deref_mut()
+    }
+
     fn parent(&self) -> Option<&Self> {
         self.parent.as_deref()
     }
@@ -403,14 +1613,67 @@ impl<'a, T: Debug + Display + Clone> LinkedResource<'a, T> for ApiResource<'a, T
     }
 
     fn with_parent(&mut self, parent: &mut ApiResource<'a, T>) -> Result<Box<Self>> {
-        match self.parent {
-            None => {
-                self.parent = Box::new(parent.clone()).into();
+        match (&self.parent, &parent.child) {
+            (None, None) => {
+                self.inherit_required(parent.arg_required_inherited);
+                self.inherit_weight(parent.weight);
+                self.parent  = Box::new(parent.clone()).into();
+                parent.child = Box::new(self.clone()).into();
                 Ok(Box::new(self.clone()))
             },
-            Some(_) => Err(ResourceError::AlreadySet(self.name(), "parent".into()).into())
+            (Some(_), _) => Err(ResourceError::AlreadySet(self.name(), "parent".into()).into()),
+            (_, Some(_)) => Err(ResourceError::AlreadySet(parent.name(), "child".into()).into())
+        }
+    }
+
+    fn attach_child(&mut self, mut child: ApiResource<'a, T>) -> Result<&mut Self> {
+        if self.child.is_some() {
+            return Err(ResourceError::AlreadySet(self.name(), "child".into()).into())
+        }
+        if child.parent.is_some() {
+            return Err(ResourceError::AlreadySet(child.name(), "parent".into()).into())
+        }
+
+        child.inherit_required(self.arg_required_inherited);
+        child.inherit_weight(self.weight);
+        child.parent = Some(Box::new(self.clone()));
+        self.child   = Some(Box::new(child));
+        Ok(self)
+    }
+
+    fn detach_child(&mut self) -> Option<ApiResource<'a, T>> {
+        self.child.take().map(|mut child| {
+            child.parent = None;
+            *child
+        })
+    }
+
+    fn find(&self, name: &str) -> Option<&Self> {
+        match self.name == name {
+            true  => Some(self),
+            false => self.child()?.find(name)
+        }
+    }
+
+    fn find_mut(&mut self, name: &str) -> Option<&mut Self> {
+        match self.name == name {
+            true  => Some(self),
+            false => self.child_mut()?.find_mut(name)
         }
     }
+
+    fn reverse(&self) -> Result<Self> {
+        let mut reversed = self.clone();
+        reversed.parent  = None;
+
+        let mut next = reversed.detach_child();
+        while let Some(mut node) = next {
+            next = node.detach_child();
+            node.attach_child(reversed)?;
+            reversed = node;
+        }
+        Ok(reversed)
+    }
 }
 
 /// Resource can be 'weighted'. This allows use
@@ -422,6 +1685,38 @@ pub trait WeightedResource {
     /// Determines the ordering weight to be used
     /// by pre-digestion sorting.
     fn with_weight(&mut self, weight: f32) -> &Self;
+    /// Determines the ordering weight, consuming
+    /// and returning this for fluent construction.
+    /// ```rust
+    /// use uri_resources::{ApiResource, WeightedResource};
+    /// let resource = ApiResource::<String>::new("resource").weighted(2.0);
+    /// assert_eq!(resource.weight(), 2.0)
+    /// ```
+    fn weighted(self, weight: f32) -> Self
+    where
+        Self: Sized;
+    /// Determines the ordering weight as an offset
+    /// from the weight of the parent, applied when
+    /// linked. Descendants with relative weights
+    /// are updated along with this.
+    /// ```rust
+    /// use uri_resources::{ApiResource, LinkedResource, WeightedResource};
+    /// let mut child0 = ApiResource::<String>::new("child_resource0");
+    /// let mut child1 = ApiResource::<String>::new("child_resource1");
+    /// child0.with_weight_relative(1.0);
+    /// child1.with_weight_relative(1.0);
+    ///
+    /// child0 = *child0.with_child(&mut child1).expect("resource node");
+    /// let parent = ApiResource::<String>::new("parent_resource")
+    ///     .weighted(5.0)
+    ///     .with_child(&mut child0)
+    ///     .expect("parent node");
+    ///
+    /// let middle = parent.child().expect("middle node");
+    /// let tail   = middle.child().expect("tail node");
+    /// assert_eq!((parent.weight(), middle.weight(), tail.weight()), (5.0, 6.0, 7.0))
+    /// ```
+    fn with_weight_relative(&mut self, offset: f32) -> &mut Self;
 }
 
 impl<T: Display> WeightedResource for ApiResource<'_, T> {
@@ -433,6 +1728,19 @@ impl<T: Display> WeightedResource for ApiResource<'_, T> {
         self.weight = weight;
         self
     }
+
+    fn weighted(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    fn with_weight_relative(&mut self, offset: f32) -> &mut Self {
+        self.weight_offset = Some(offset);
+        if let Some(weight) = self.parent.as_ref().map(|p| p.weight) {
+            self.inherit_weight(weight);
+        }
+        self
+    }
 }
 
 pub trait Resource<'a, T: Clone + Display>:
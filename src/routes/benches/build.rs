@@ -0,0 +1,44 @@
+use std::{hint::black_box, time::Instant};
+
+use uri_routes::{ApiRouteBuilder, RouteBuilder};
+
+const ITERATIONS: u32 = 100;
+const INSERTS: usize  = 500;
+
+fn bench(name: &str, f: impl Fn()) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    println!("{name:<24} {:?}/iter", start.elapsed() / ITERATIONS);
+}
+
+fn main() {
+    bench("many_inserts", || {
+        let mut route = ApiRouteBuilder::new("fqdm.org");
+        for i in 0..INSERTS {
+            route = route
+                .with_path_weight(format!("p{i}"), (INSERTS - i) as f32)
+                .with_param(format!("k{i}"), i);
+        }
+        black_box(route.build().unwrap());
+    });
+
+    bench("many_inserts_reserved", || {
+        let mut route = ApiRouteBuilder::with_capacity("fqdm.org", INSERTS, INSERTS);
+        for i in 0..INSERTS {
+            route = route
+                .with_path_weight(format!("p{i}"), (INSERTS - i) as f32)
+                .with_param(format!("k{i}"), i);
+        }
+        black_box(route.build().unwrap());
+    });
+
+    bench("weighted_paths", || {
+        let mut route = ApiRouteBuilder::new("fqdm.org");
+        for i in (0..INSERTS).rev() {
+            route = route.with_path_weight(format!("p{i}"), i as f32);
+        }
+        black_box(route.build().unwrap());
+    });
+}
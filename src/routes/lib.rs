@@ -1,3 +1,7 @@
+use std::{borrow::Cow, path::{Component, Path, PathBuf}, sync::Arc};
+
+#[cfg(feature = "hmac")]
+use hmac::Mac;
 use http::uri;
 use ordered_float::OrderedFloat;
 
@@ -10,34 +14,353 @@ pub trait RouteBuilder<'a> {
     fn new(host: &'a str) -> Self;
     /// Tries to build a URI from path arguments
     /// and parameters.
-    fn build(self) -> Result<uri::Uri, http::Error>;
+    fn build(self) -> Result<uri::Uri, RouteBuildError>;
+    /// Tries to build a URI for the primary
+    /// scheme followed by each fallback scheme.
+    fn build_all(self) -> Vec<Result<uri::Uri, RouteBuildError>>;
+    /// Renders the URI as a string without
+    /// validating it.
+    fn build_string(&self) -> String;
+    /// Renders the path and query as a route
+    /// template without validating it.
+    fn build_template(self) -> String;
+    /// Whether the rendered URI equals the
+    /// expected string.
+    fn matches(&self, expected: &str) -> bool;
     /// Add a parameter key/pair to the builder.
     fn with_param<T: ToString>(self, name: String, value: T) -> Self;
     /// Add a path argument to the end of the
-    /// path buffer.
+    /// path buffer. Characters other than `/`
+    /// and those unreserved by RFC 3986 are
+    /// percent-encoded.
     fn with_path(self, path: String) -> Self;
     /// Inserts a path argument with the desired
     /// weight.
     fn with_path_weight(self, path: String, weight: f32) -> Self;
     /// Set the protocol scheme.
     fn with_scheme(self, scheme: String) -> Self;
+    /// Splits a path on `/`, adding each
+    /// non-empty segment as its own path
+    /// argument with ascending weights.
+    fn with_path_segments(self, path: &'a str) -> Self;
+    /// Attach an already encoded query string,
+    /// appended verbatim after any parameters.
+    fn with_raw_query(self, query: &'a str) -> Self;
+    /// Inserts each of a collection of weighted
+    /// path arguments.
+    fn with_route_paths<I: IntoIterator<Item = ApiRoutePath>>(self, paths: I) -> Self;
+    /// Add each path argument, in order, to the
+    /// end of the path buffer.
+    fn with_paths(self, paths: impl IntoIterator<Item = &'a str>) -> Self;
+    /// Set the protocol scheme along with a
+    /// fallback scheme.
+    fn with_scheme_fallback(self, primary: &'a str, fallback: &'a str) -> Self;
+    /// Set the protocol scheme used when no
+    /// scheme has been set explicitly.
+    fn with_scheme_default(self, scheme: &'a str) -> Self;
+    /// Omit the protocol scheme, producing a
+    /// scheme-relative reference.
+    fn with_scheme_relative(self) -> Self;
+    /// Add a path argument, with matrix
+    /// parameters, to the end of the path buffer.
+    fn with_path_matrix(self, path: &'a str, matrix: &[(&str, &str)]) -> Self;
+    /// Add a placeholder path argument, rendered
+    /// as `{name}`, to the end of the path buffer.
+    fn with_path_wildcard(self, name: &'a str) -> Self;
+    /// Set the path of the fragment, for
+    /// client-side routing.
+    fn with_fragment_path(self, path: &'a str) -> Self;
+    /// Add a parameter key/pair to the fragment.
+    fn with_fragment_param<T: ToString>(self, name: &'a str, value: T) -> Self;
+    /// Set the host of the builder, replacing
+    /// any existing host.
+    fn with_host(self, host: &'a str) -> Self;
+    /// Add an already encoded path argument to
+    /// the end of the path buffer, bypassing
+    /// percent-encoding.
+    fn with_path_raw(self, path: &'a str) -> Self;
+    /// Number of path segments, excluding the
+    /// root, the built URI will contain.
+    fn segment_count(&self) -> usize;
+    /// Number of query parameters the built URI
+    /// will contain.
+    fn param_count(&self) -> usize;
+    /// A canonical key for the route, stable
+    /// regardless of parameter insertion order.
+    fn cache_key(&self) -> String;
+    /// Merges another builder onto the end of
+    /// this one.
+    fn join(self, other: Self) -> Self;
+    /// Removes path arguments which repeat the
+    /// path argument before them.
+    fn dedup_adjacent(self) -> Self;
+    /// Set the port of the authority.
+    fn with_port(self, port: u16) -> Self;
+    /// Set the userinfo of the authority.
+    fn with_userinfo(self, userinfo: &'a str) -> Self;
+    /// The authority, `[userinfo@]host[:port]`,
+    /// of the built URI.
+    fn authority(&self) -> String;
+    /// Decomposes the builder into its owned
+    /// parts.
+    fn into_parts(self) -> RouteParts;
+    /// Set the character which separates query
+    /// parameters.
+    fn with_param_separator(self, sep: char) -> Self;
+    /// Checks the host is not empty, contains no
+    /// illegal characters and does not start or
+    /// end with a dot.
+    fn validate_host(&self) -> Result<(), RouteBuildError>;
+    /// Set whether the path ends with a slash.
+    fn with_trailing_slash(self, enabled: bool) -> Self;
+    /// Rewrites each path argument, other than
+    /// the root, preserving their weights.
+    fn map_paths(self, f: impl Fn(&str) -> String) -> Self;
+    /// Whether the rendered URI equals another,
+    /// ignoring differences in percent-encoding.
+    fn semantic_eq(&self, other: &str) -> bool;
+    /// Add a parameter without a value.
+    fn with_flag(self, name: &'a str) -> Self;
+    /// Removes a leading prefix from the path,
+    /// doing nothing if the path does not start
+    /// with it.
+    fn strip_prefix(self, prefix: &str) -> Self;
+    /// Emit query parameters sorted by name, then
+    /// value.
+    fn with_sorted_params(self) -> Self;
+    /// Removes every query parameter.
+    fn clear_params(self) -> Self;
+    /// Removes every path argument, other than
+    /// the root.
+    fn clear_paths(self) -> Self;
+    /// Add a path argument before all others,
+    /// after the root.
+    fn prepend_path(self, path: &'a str) -> Self;
+    /// Add a parameter only when a value is
+    /// given.
+    fn with_param_opt<T: ToString>(self, name: &'a str, value: Option<T>) -> Self;
+    /// Add a path argument to the end of the
+    /// path buffer, leaving additional safe
+    /// characters unencoded.
+    fn with_path_safe(self, path: &'a str, safe: &'a str) -> Self;
+    /// Tries to build a URI no longer than the
+    /// given number of bytes.
+    fn build_checked(self, max_len: usize) -> Result<uri::Uri, RouteBuildError>;
+    /// Each path argument, other than the root,
+    /// in the order they are built, paired with
+    /// the order they were added and their weight.
+    fn debug_trace(&self) -> Vec<(usize, String, f32)>;
+    /// Set an opaque scheme, such as `mailto`,
+    /// and its content, replacing the path.
+    fn with_opaque(self, scheme: &'a str, content: &'a str) -> Self;
+    /// Set the string which separates path
+    /// arguments.
+    fn with_separator(self, sep: &'a str) -> Self;
+    /// Add an already encoded parameter value,
+    /// which bypasses percent-encoding.
+    fn with_param_raw<T: ToString>(self, name: &'a str, value: T) -> Self;
+    /// Add a single parameter whose values are
+    /// joined by commas.
+    fn with_param_csv<T: ToString>(self, name: &'a str, values: impl IntoIterator<Item = T>) -> Self;
+    /// Reports what would be built, and the
+    /// problems which would prevent it, without
+    /// building.
+    fn explain(&self) -> RouteExplanation;
+    /// Set whether spaces in parameter values are
+    /// encoded as `+` rather than `%20`.
+    fn with_space_as_plus(self, enabled: bool) -> Self;
+    /// Set the whole authority, overriding the
+    /// host, port and userinfo.
+    fn with_authority(self, authority: &'a str) -> Self;
+    /// Keep only the parameters for which the
+    /// predicate, given each name and value,
+    /// holds.
+    fn retain_params(self, f: impl Fn(&str, &str) -> bool) -> Self;
+    /// Fail to build when any path argument,
+    /// other than the root, is empty.
+    fn with_strict_segments(self) -> Self;
+    /// Set the scheme to `wss`, or `ws` when not
+    /// secure.
+    fn with_scheme_ws(self, secure: bool) -> Self;
+    /// Merge parameters into the builder,
+    /// resolving those which share a name by the
+    /// given strategy.
+    fn merge_params(self, other: &[(&str, &str)], strategy: MergeStrategy) -> Self;
+    /// Renders only the path and query, omitting
+    /// the scheme and authority.
+    fn relative_ref(&self) -> String;
+    /// Insert a path argument between two
+    /// adjacent path arguments, weighted midway
+    /// between them.
+    fn insert_between(self, path: &'a str, after: &str, before: &str) -> Result<Self, RouteBuildError>
+    where
+        Self: Sized;
+    /// Add a boolean parameter, rendered in the
+    /// given style.
+    fn with_param_bool(self, name: &'a str, value: bool, style: BoolStyle) -> Self;
+    /// The path arguments as a relative
+    /// filesystem path, one component per
+    /// segment, with percent-encoding decoded.
+    /// Fails on segments which are not a single,
+    /// plain, path component.
+    fn to_path_buf(&self) -> Result<PathBuf, RouteBuildError>;
+}
+
+/// Failures which can occur while building a
+/// route, by the component which caused them.
+/// ```rust
+/// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder, RouteBuildError};
+/// let error = ApiRouteBuilder::new("fqdm.org")
+///     .with_scheme("ht tp".into())
+///     .build();
+/// assert!(matches!(error, Err(RouteBuildError::InvalidScheme(_))));
+///
+/// let error = ApiRouteBuilder::new("fqdm org").build();
+/// assert!(matches!(error, Err(RouteBuildError::InvalidHost(..))));
+///
+/// let error = ApiRouteBuilder::new("fqdm.org")
+///     .with_path_raw("a b")
+///     .build();
+/// assert!(matches!(error, Err(RouteBuildError::InvalidPath(_))));
+///
+/// let error = ApiRouteBuilder::new("fqdm.org")
+///     .with_scheme("data".into())
+///     .build();
+/// assert!(matches!(error, Err(RouteBuildError::OpaqueScheme(_))));
+///
+/// let error = http::Request::builder()
+///     .method("not a method")
+///     .body(())
+///     .unwrap_err();
+/// assert!(matches!(error.into(), RouteBuildError::Http(_)));
+/// ```
+#[derive(thiserror::Error, Debug)]
+pub enum RouteBuildError {
+    #[error("invalid host {0:?}: {1}")]
+    InvalidHost(String, String),
+    #[error("invalid path {0:?}")]
+    InvalidPath(String),
+    #[error("invalid scheme {0:?}")]
+    InvalidScheme(String),
+    #[error("opaque scheme {0:?} cannot be built with an authority")]
+    OpaqueScheme(String),
+    #[error("no path argument {0:?}")]
+    PathNotFound(String),
+    #[error("path arguments {0:?} and {1:?} are not adjacent")]
+    PathsNotAdjacent(String, String),
+    #[error("uri is {len} bytes, longer than the maximum of {max}")]
+    TooLong{ len: usize, max: usize },
+    #[error(transparent)]
+    Http(#[from] http::Error),
 }
 
-#[derive(Clone, Eq)]
-struct ApiRoutePath {
-    path:   String,
-    weight: OrderedFloat<f32>,
+/// A single, weighted, path argument. Paths
+/// are ordered by their weight alone.
+/// ```rust
+/// use crate::uri_routes::ApiRoutePath;
+/// let mut paths = vec![
+///     ApiRoutePath::new("users".into(), 2.0),
+///     ApiRoutePath::new("api".into(), 1.0),
+///     ApiRoutePath::new("list".into(), 3.0),
+/// ];
+/// paths.sort();
+///
+/// let paths: Vec<_> = paths.iter().map(|p| p.path()).collect();
+/// assert_eq!(paths, vec!["api", "users", "list"])
+/// ```
+#[derive(Clone, Debug, Eq)]
+pub struct ApiRoutePath {
+    path:     String,
+    weight:   OrderedFloat<f32>,
+    encoded:  bool,
+    index:    usize,
+    matrix:   Vec<(String, String)>,
+    root:     bool,
+    safe:     String,
+    wildcard: bool,
 }
 
 impl ApiRoutePath {
+    /// New instance of an `ApiRoutePath`.
     pub fn new(path: String, weight: f32) -> Self {
-        Self{path: path.to_owned(), weight: OrderedFloat::from(weight)}
+        Self{
+            path: path.to_owned(),
+            weight: OrderedFloat::from(weight),
+            encoded: false,
+            index: 0,
+            matrix: vec![],
+            root: false,
+            safe: String::from("/"),
+            wildcard: false
+        }
+    }
+
+    /// New instance of an `ApiRoutePath` which
+    /// is already percent-encoded.
+    pub fn new_encoded(path: String, weight: f32) -> Self {
+        Self{encoded: true, ..Self::new(path, weight)}
+    }
+
+    /// The root path every builder is seeded
+    /// with, ordered before all others.
+    fn root() -> Self {
+        Self{root: true, ..Self::new(String::from("/"), 0.0)}
+    }
+
+    /// Whether this path is already encoded and
+    /// should bypass percent-encoding.
+    pub fn is_encoded(&self) -> bool {
+        self.encoded
+    }
+
+    /// Whether this path is a placeholder within
+    /// a route template.
+    pub fn is_wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    /// Matrix parameters attached to this path.
+    pub fn matrix(&self) -> &[(String, String)] {
+        &self.matrix
+    }
+
+    /// Whether this is the root path every
+    /// builder is seeded with.
+    fn is_root(&self) -> bool {
+        self.root
+    }
+
+    /// Renders this path as it appears in the
+    /// built URI.
+    fn render(&self) -> String {
+        let mut rendered = match (self.wildcard, self.encoded) {
+            (true, _)      => format!("{{{}}}", self.path),
+            (false, true)  => self.path.clone(),
+            (false, false) => percent_encode(&self.path, &format!("{PATH_SAFE}{}", self.safe))
+        };
+        for (name, value) in &self.matrix {
+            rendered.push_str(&format!(
+                ";{}={}",
+                percent_encode(name, ""),
+                percent_encode(value, "")));
+        }
+        rendered
+    }
+
+    /// The path argument itself.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The sorting weight of this path.
+    pub fn weight(&self) -> f32 {
+        self.weight.into_inner()
     }
 }
 
 impl Ord for ApiRoutePath {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.weight.cmp(&other.weight)
+        other.root.cmp(&self.root).then(self.weight.cmp(&other.weight))
     }
 }
 
@@ -59,32 +382,116 @@ impl PartialOrd for ApiRoutePath {
     }
 }
 
-impl ToString for ApiRoutePath {
-    fn to_string(&self) -> String {
-        self.path.clone()
+impl std::fmt::Display for ApiRoutePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path)
+    }
+}
+
+/// A single query parameter. Its name and value
+/// are kept as given, and percent-encoded when
+/// rendered unless the value is raw.
+#[derive(Clone, Debug)]
+struct ApiRouteParam {
+    name:  String,
+    value: Option<String>,
+    raw:   bool,
+}
+
+impl ApiRouteParam {
+    /// Renders this parameter as it appears in
+    /// the built URI.
+    fn render(&self, space_as_plus: bool) -> String {
+        let name  = percent_encode(&self.name, "");
+        let value = match (&self.value, self.raw, space_as_plus) {
+            (None, ..)              => return name,
+            (Some(v), true, _)      => v.clone(),
+            (Some(v), false, true)  => percent_encode(v, " ").replace(' ', "+"),
+            (Some(v), false, false) => percent_encode(v, "")
+        };
+        format!("{name}={value}")
     }
 }
 
+/// Builds a URI from its components. Clones are
+/// independent, so a configured builder can be
+/// reused as a template.
+/// ```rust
+/// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+/// let template = ApiRouteBuilder::new("fqdm.org").with_path("users".into());
+/// let route = template.clone().with_path("42".into()).with_param("page".into(), 2);
+///
+/// assert_eq!(template.build_string(), "https://fqdm.org/users");
+/// assert_eq!(route.build_string(), "https://fqdm.org/users/42?page=2")
+/// ```
+#[derive(Clone)]
 pub struct ApiRouteBuilder<'a> {
-    hostname:   &'a str,
-    parameters: Vec<String>,
-    scheme:     Option<String>,
-    sub_paths:  Vec<ApiRoutePath>,
+    authority:       Option<&'a str>,
+    fragment_params: Vec<String>,
+    fragment_path:   Option<String>,
+    hostname:        Cow<'a, str>,
+    param_separator: char,
+    parameters:      Vec<ApiRouteParam>,
+    path_inserts:    usize,
+    paths_sorted:    bool,
+    port:            Option<u16>,
+    raw_query:       Option<&'a str>,
+    scheme:          Option<String>,
+    scheme_default:  Option<String>,
+    scheme_fallback: Option<String>,
+    separator:       &'a str,
+    sorted_params:   bool,
+    space_as_plus:   bool,
+    strict_segments: bool,
+    sub_paths:       Vec<ApiRoutePath>,
+    trailing_slash:  bool,
+    userinfo:        Option<String>,
 }
 
 impl<'a> ApiRouteBuilder<'a> {
+    fn build_scheme(&self, scheme: String) -> Result<uri::Uri, RouteBuildError> {
+        let scheme         = self.validate_scheme(scheme)?;
+        let authority      = self.validate_authority(scheme.as_str())?;
+        let path_and_query = self.validate_path()?;
+
+        Ok(uri::Builder::new()
+            .scheme(scheme)
+            .authority(authority)
+            .path_and_query(path_and_query)
+            .build()?)
+    }
+
     fn insert_param<T: ToString>(mut self, name: String, value: T) -> Self {
-        self.parameters.push(format!("{name}={}", value.to_string()));
+        self.parameters.push(ApiRouteParam{name, value: Some(value.to_string()), raw: false});
+        self
+    }
+
+    fn insert_param_raw<T: ToString>(mut self, name: &str, value: T) -> Self {
+        self.parameters.push(ApiRouteParam{name: name.to_owned(), value: Some(value.to_string()), raw: true});
         self
     }
 
-    fn insert_path(mut self, path: String, weight: Option<f32>) -> Self {
+    fn insert_path(self, path: String, weight: Option<f32>) -> Self {
         let weight = weight
             .unwrap_or(f32::MAX)
             .clamp(0.1, f32::MAX);
-        let path = ApiRoutePath::new(path, weight);
+        self.insert_route_path(ApiRoutePath::new(path, weight))
+    }
+
+    fn insert_paths<I: IntoIterator<Item = String>>(mut self, paths: I) -> Self {
+        let mut weight = self.last_weight();
+        for path in paths {
+            weight += 1.0;
+            self = self.insert_path(path, Some(weight));
+        }
+        self
+    }
+
+    fn insert_route_path(mut self, mut path: ApiRoutePath) -> Self {
+        path.index = self.path_inserts;
+        self.path_inserts += 1;
         self.sub_paths.push(path);
-        self.sub_paths.sort();
+        self.paths_sorted = false;
         self
     }
 
@@ -93,64 +500,349 @@ impl<'a> ApiRouteBuilder<'a> {
         self
     }
 
+    fn last_weight(&self) -> f32 {
+        self.sub_paths
+            .iter()
+            .max()
+            .map_or(0.0, |p| p.weight())
+    }
+
+    fn parse_authority(&self, scheme: &str) -> String {
+        if let Some(authority) = self.authority {
+            return authority.to_owned();
+        }
+        let mut authority = self.parse_host();
+        if let Some(userinfo) = self.userinfo.as_deref().or(self.split_userinfo().0) {
+            authority = format!("{userinfo}@{authority}");
+        }
+        if let Some(port) = self.port.filter(|p| Some(*p) != default_port(scheme)) {
+            authority = format!("{authority}:{port}");
+        }
+        authority
+    }
+
+    fn parse_fragment(&self) -> String {
+        let path   = self.fragment_path.clone().unwrap_or_default();
+        let params = self.fragment_params.join("&");
+
+        match (path.is_empty(), params.is_empty()) {
+            (true, true) => String::new(),
+            (_, true)    => format!("#{path}"),
+            (_, false)   => format!("#{path}?{params}")
+        }
+    }
+
+    #[cfg(feature = "idna")]
+    fn parse_host(&self) -> String {
+        let (_, host) = self.split_userinfo();
+        idna::domain_to_ascii(host).unwrap_or_else(|_| host.to_lowercase())
+    }
+
+    #[cfg(not(feature = "idna"))]
+    fn parse_host(&self) -> String {
+        self.split_userinfo().1.to_lowercase()
+    }
+
+    /// Renders the scheme and authority, as they
+    /// precede the path. Opaque schemes have no
+    /// authority, and scheme-relative references
+    /// no scheme.
+    fn parse_origin(&self) -> String {
+        let scheme = self.parse_scheme();
+        if is_opaque_scheme(&scheme) {
+            return format!("{scheme}:");
+        }
+
+        let authority = self.parse_authority(&scheme);
+        match scheme.is_empty() {
+            true  => format!("//{authority}"),
+            false => format!("{scheme}://{authority}")
+        }
+    }
+
     fn parse_params(&self) -> String {
-        self.parameters.join("&")
+        self.parse_query_entries().join(&self.param_separator.to_string())
     }
 
     fn parse_path(&self) -> String {
         let mut paths = self.sub_paths.clone();
-        paths.retain(|p| p != "");
+        paths.retain(|p| p.is_root() || !p.path.trim().is_empty());
+        if !self.paths_sorted {
+            paths.sort();
+        }
+
+        if is_opaque_scheme(&self.parse_scheme()) {
+            let paths: Vec<_> = paths
+                .iter()
+                .filter(|p| !p.is_root())
+                .map(|p| match p.encoded {
+                    true  => p.path.clone(),
+                    false => percent_encode(&p.path, &format!("{PATH_SAFE}/{}", p.safe))
+                })
+                .collect();
+            return paths.join("/");
+        }
 
         let paths: Vec<_> = paths
             .iter()
-            .map(|p| p.to_string())
+            .filter(|p| !p.is_root())
+            .map(|p| p.render())
+            .filter(|p| !p.is_empty())
+            .collect();
+        let path = match self.separator {
+            "/" => format!("/{}", paths.join("/")).replace("//", "/"),
+            sep => format!("/{}", paths.join(sep))
+        };
+
+        match self.trailing_slash {
+            true  => format!("{}/", path.trim_end_matches('/')),
+            false => path
+        }
+    }
+
+    fn parse_path_and_query(&self) -> String {
+        let path   = self.parse_path();
+        let params = self.parse_params();
+
+        match params.is_empty() {
+            true  => path,
+            false => format!("{path}?{params}")
+        }
+    }
+
+    fn parse_query_entries(&self) -> Vec<String> {
+        let mut params: Vec<_> = self.parameters
+            .iter()
+            .map(|p| p.render(self.space_as_plus))
             .collect();
-        paths.join("/").replace("//", "/")
+        if let Some(query) = self.raw_query {
+            params.extend(query
+                .trim_start_matches('?')
+                .split(self.param_separator)
+                .map(String::from));
+        }
+        params.retain(|p| !p.is_empty());
+        if self.sorted_params {
+            params.sort_by(|a, b| split_param(a).cmp(&split_param(b)));
+        }
+        params
     }
 
     fn parse_scheme(&self) -> String {
-        self.scheme.clone().unwrap_or(String::from("https"))
+        self.scheme
+            .clone()
+            .or(self.scheme_default.clone())
+            .unwrap_or(String::from("https"))
+            .to_lowercase()
+    }
+
+    fn sort_paths(&mut self) {
+        if !self.paths_sorted {
+            self.sub_paths.sort();
+            self.paths_sorted = true;
+        }
+    }
+
+    /// Splits userinfo given as part of the host,
+    /// as in `user@host`, from the host itself.
+    fn split_userinfo(&self) -> (Option<&str>, &str) {
+        match self.hostname.rsplit_once('@') {
+            Some((userinfo, host)) => (Some(userinfo), host),
+            None                   => (None, self.hostname.as_ref())
+        }
+    }
+
+    fn validate_authority(&self, scheme: &str) -> Result<uri::Authority, RouteBuildError> {
+        if self.authority.is_none() {
+            self.validate_host()?;
+        }
+        let authority = self.parse_authority(scheme);
+        uri::Authority::try_from(authority.as_str())
+            .map_err(|e| RouteBuildError::InvalidHost(authority.clone(), e.to_string()))
+    }
+
+    fn validate_path(&self) -> Result<uri::PathAndQuery, RouteBuildError> {
+        let path_and_query = self.parse_path_and_query();
+        let has_empty = self.sub_paths
+            .iter()
+            .any(|p| !p.is_root() && p.path.trim().is_empty());
+        if self.strict_segments && has_empty {
+            return Err(RouteBuildError::InvalidPath(path_and_query));
+        }
+
+        uri::PathAndQuery::try_from(path_and_query.as_str())
+            .map_err(|_| RouteBuildError::InvalidPath(path_and_query.clone()))
+    }
+
+    fn validate_scheme(&self, scheme: String) -> Result<uri::Scheme, RouteBuildError> {
+        match (scheme.is_empty(), is_opaque_scheme(&scheme)) {
+            (true, _)  => Err(RouteBuildError::InvalidScheme(scheme)),
+            (_, true)  => Err(RouteBuildError::OpaqueScheme(scheme)),
+            (_, false) => uri::Scheme::try_from(scheme.as_str())
+                .map_err(|_| RouteBuildError::InvalidScheme(scheme.clone()))
+        }
+    }
+}
+
+/// A builder with an empty host. A host must be
+/// set with `with_host` before building, as an
+/// empty host fails to build.
+/// ```rust
+/// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+/// assert!(ApiRouteBuilder::default().build().is_err())
+/// ```
+impl Default for ApiRouteBuilder<'_> {
+    fn default() -> Self {
+        Self::new("")
     }
 }
 
 impl<'a> RouteBuilder<'a> for ApiRouteBuilder<'a> {
     fn new(host: &'a str) -> Self {
         Self{
-            hostname: host,
+            authority: None,
+            fragment_params: vec![],
+            fragment_path: None,
+            hostname: Cow::Borrowed(host),
+            param_separator: '&',
             parameters: vec![],
+            path_inserts: 0,
+            paths_sorted: true,
+            port: None,
+            raw_query: None,
             scheme: None,
-            sub_paths: vec![ApiRoutePath::new(String::from("/"), 0.0)]
+            scheme_default: None,
+            scheme_fallback: None,
+            separator: "/",
+            sorted_params: false,
+            space_as_plus: false,
+            strict_segments: false,
+            sub_paths: vec![ApiRoutePath::root()],
+            trailing_slash: false,
+            userinfo: None
         }
     }
 
     /// Tries to build a URI from path arguments
-    /// and parameters.
+    /// and parameters. Path arguments are sorted
+    /// by weight once, here, rather than as they
+    /// are added.
     /// ```rust
     /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
     /// let route = ApiRouteBuilder::new("google.com").build().unwrap();
-    /// assert_eq!(route, "https://google.com")
+    /// assert_eq!(route, "https://google.com");
+    ///
+    /// let mut route = ApiRouteBuilder::new("fqdm.org");
+    /// for i in (1..=500).rev() {
+    ///     route = route.with_path_weight(i.to_string(), i as f32);
+    /// }
+    /// let expected: Vec<_> = (1..=500).map(|i| i.to_string()).collect();
+    /// let route = route.build().unwrap();
+    /// assert_eq!(route.path(), format!("/{}", expected.join("/")))
+    /// ```
+    fn build(mut self) -> Result<uri::Uri, RouteBuildError> {
+        self.sort_paths();
+        self.build_scheme(self.parse_scheme())
+    }
+
+    /// Builds a URI for the primary scheme and
+    /// then each fallback scheme.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let routes: Vec<_> = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_scheme_fallback("https", "http")
+    ///     .build_all()
+    ///     .into_iter()
+    ///     .map(|r| r.unwrap())
+    ///     .collect();
+    /// assert_eq!(routes[0], "https://fqdm.org");
+    /// assert_eq!(routes[1], "http://fqdm.org");
+    /// ```
+    fn build_all(mut self) -> Vec<Result<uri::Uri, RouteBuildError>> {
+        self.sort_paths();
+        let mut schemes = vec![self.parse_scheme()];
+        schemes.extend(self.scheme_fallback.iter().map(|s| s.to_lowercase()));
+
+        schemes
+            .into_iter()
+            .map(|scheme| self.build_scheme(scheme))
+            .collect()
+    }
+
+    /// Renders the URI as a string without
+    /// validating it. Opaque schemes, such as
+    /// `data`, are rendered without an authority
+    /// and their path is left as is.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("resource".into())
+    ///     .with_param("page".into(), 1);
+    /// assert_eq!(route.build_string(), "https://fqdm.org/resource?page=1");
+    ///
+    /// let route = ApiRouteBuilder::default()
+    ///     .with_scheme("data".into())
+    ///     .with_path("text/plain,hello".into());
+    /// assert_eq!(route.build_string(), "data:text/plain,hello")
+    /// ```
+    fn build_string(&self) -> String {
+        format!(
+            "{}{}{}",
+            self.parse_origin(),
+            self.parse_path_and_query(),
+            self.parse_fragment())
+    }
+
+    /// Renders the path and query as a route
+    /// template without validating it.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let template = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("users".into())
+    ///     .with_path_wildcard("id")
+    ///     .with_path("posts".into())
+    ///     .build_template();
+    /// assert_eq!(template, "/users/{id}/posts")
     /// ```
-    fn build(self) -> Result<uri::Uri, http::Error> {
-        let scheme   = self.parse_scheme();
-        let hostname = self.hostname;
-        let path     = self.parse_path();
-        let params   = self.parse_params();
+    fn build_template(self) -> String {
+        self.parse_path_and_query()
+    }
 
-        uri::Builder::new()
-            .scheme(scheme.as_str())
-            .authority(hostname)
-            .path_and_query(format!("{path}?{params}"))
-            .build()
+    /// Whether the rendered URI equals the
+    /// expected string. When no scheme has been
+    /// set, the expected string may omit the
+    /// default scheme.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("resource".into())
+    ///     .with_param("page".into(), 1);
+    /// assert!(route.matches("https://fqdm.org/resource?page=1"));
+    /// assert!(route.matches("fqdm.org/resource?page=1"));
+    /// assert!(!route.matches("http://fqdm.org/resource?page=1"));
+    ///
+    /// let route = route.with_scheme("http".into());
+    /// assert!(!route.matches("fqdm.org/resource?page=1"));
+    /// ```
+    fn matches(&self, expected: &str) -> bool {
+        match self.scheme.is_none() && !expected.contains("://") {
+            true  => self.build_string() == format!("{}://{expected}", self.parse_scheme()),
+            false => self.build_string() == expected
+        }
     }
 
     /// Add a parameter key/pair to the builder.
+    /// Both are percent-encoded.
     /// ```rust
     /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
     /// let route = ApiRouteBuilder::new("fqdm.org")
     ///     .with_param("page".into(), 1)
     ///     .build()
     ///     .unwrap();
-    /// assert_eq!(route, "https://fqdm.org?page=1")
+    /// assert_eq!(route, "https://fqdm.org?page=1");
+    ///
+    /// let route = ApiRouteBuilder::new("fqdm.org").with_param("q".into(), "a&b=c");
+    /// assert_eq!(route.build_string(), "https://fqdm.org/?q=a%26b%3Dc")
     /// ```
     fn with_param<T: ToString>(self, name: String, value: T) -> Self {
         self.insert_param(name, value)
@@ -166,6 +858,19 @@ impl<'a> RouteBuilder<'a> for ApiRouteBuilder<'a> {
     ///     .unwrap();
     /// assert_eq!(route, "https://fqdm.org/resource")
     /// ```
+    ///
+    /// Characters not allowed in a path segment
+    /// are percent-encoded. Sub-delimiters, `:`
+    /// and `@` are not.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("my resource/50%".into())
+    ///     .with_path("a:b;v=1".into())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org/my%20resource/50%25/a:b;v=1")
+    /// ```
     fn with_path(self, path: String) -> Self {
         self.insert_path(path, None)
     }
@@ -195,7 +900,1705 @@ impl<'a> RouteBuilder<'a> for ApiRouteBuilder<'a> {
     ///     .unwrap();
     /// assert_eq!(route, "file://localhost")
     /// ```
+    ///
+    /// Scheme and host are case-insensitive and
+    /// are normalized to lowercase.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("Example.COM")
+    ///     .with_scheme("HTTPS".into())
+    ///     .with_path("Resource".into())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route.to_string(), "https://example.com/Resource")
+    /// ```
     fn with_scheme(self, scheme: String) -> Self {
         self.insert_scheme(Some(scheme.to_owned()))
     }
+
+    /// Splits a path on `/`, adding each
+    /// non-empty segment as its own path
+    /// argument with ascending weights.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let segmented = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path_segments("a//b/c/")
+    ///     .build()
+    ///     .unwrap();
+    /// let single = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("a/b/c".into())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(segmented, single);
+    /// assert_eq!(segmented, "https://fqdm.org/a/b/c")
+    /// ```
+    fn with_path_segments(self, path: &'a str) -> Self {
+        let segments = path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(String::from);
+        self.insert_paths(segments)
+    }
+
+    /// Attach an already encoded query string,
+    /// appended verbatim after any parameters.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_raw_query("q=a%20b&sort=asc")
+    ///     .with_param("page".into(), 1)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org?page=1&q=a%20b&sort=asc")
+    /// ```
+    fn with_raw_query(mut self, query: &'a str) -> Self {
+        self.raw_query = Some(query);
+        self
+    }
+
+    /// Inserts each of a collection of weighted
+    /// path arguments.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder, ApiRoutePath};
+    /// let plan = vec![
+    ///     ApiRoutePath::new("users".into(), 2.0),
+    ///     ApiRoutePath::new("api".into(), 1.0),
+    /// ];
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_route_paths(plan)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org/api/users")
+    /// ```
+    fn with_route_paths<I: IntoIterator<Item = ApiRoutePath>>(mut self, paths: I) -> Self {
+        for mut path in paths {
+            path.weight = path.weight.clamp(0.1.into(), f32::MAX.into());
+            self = self.insert_route_path(path);
+        }
+        self
+    }
+
+    /// Add each path argument, in order, to the
+    /// end of the path buffer.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_paths(["a", "b", "c"])
+    ///     .with_paths([])
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org/a/b/c")
+    /// ```
+    fn with_paths(self, paths: impl IntoIterator<Item = &'a str>) -> Self {
+        self.insert_paths(paths.into_iter().map(String::from))
+    }
+
+    /// Set the protocol scheme along with a
+    /// fallback scheme. `build` only uses the
+    /// primary scheme.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_scheme_fallback("https", "http")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org")
+    /// ```
+    fn with_scheme_fallback(mut self, primary: &'a str, fallback: &'a str) -> Self {
+        self.scheme_fallback = Some(fallback.to_owned());
+        self.insert_scheme(Some(primary.to_owned()))
+    }
+
+    /// Set the protocol scheme used when no
+    /// scheme has been set explicitly. Otherwise
+    /// `https` is used.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("localhost")
+    ///     .with_scheme_default("http")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "http://localhost");
+    ///
+    /// let route = ApiRouteBuilder::new("localhost")
+    ///     .with_scheme_default("http")
+    ///     .with_scheme("https".into())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://localhost")
+    /// ```
+    fn with_scheme_default(mut self, scheme: &'a str) -> Self {
+        self.scheme_default = Some(scheme.to_owned());
+        self
+    }
+
+    /// Omit the protocol scheme, producing a
+    /// scheme-relative reference. These can only
+    /// be rendered with `build_string`, as `build`
+    /// requires a scheme.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("example.com")
+    ///     .with_path("path".into())
+    ///     .with_scheme_relative();
+    /// assert_eq!(route.build_string(), "//example.com/path");
+    /// assert!(route.build().is_err())
+    /// ```
+    fn with_scheme_relative(self) -> Self {
+        self.insert_scheme(Some(String::new()))
+    }
+
+    /// Add a path argument, with matrix
+    /// parameters, to the end of the path buffer.
+    /// Matrix names and values are encoded.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path_matrix("seg", &[("a", "1"), ("b", "2")])
+    ///     .with_path("list".into())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org/seg;a=1;b=2/list")
+    /// ```
+    fn with_path_matrix(self, path: &'a str, matrix: &[(&str, &str)]) -> Self {
+        let mut path = ApiRoutePath::new(path.to_owned(), f32::MAX);
+        path.matrix = matrix
+            .iter()
+            .map(|(n, v)| (n.to_string(), v.to_string()))
+            .collect();
+        self.insert_route_path(path)
+    }
+
+    /// Add a placeholder path argument, rendered
+    /// as `{name}`, to the end of the path buffer.
+    /// Routes with placeholders are templates,
+    /// and are rendered with `build_template`.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("users".into())
+    ///     .with_path_wildcard("id");
+    /// assert_eq!(route.build_string(), "https://fqdm.org/users/{id}")
+    /// ```
+    fn with_path_wildcard(self, name: &'a str) -> Self {
+        let mut path = ApiRoutePath::new(name.to_owned(), f32::MAX);
+        path.wildcard = true;
+        self.insert_route_path(path)
+    }
+
+    /// Set the path of the fragment, for
+    /// client-side routing. Fragments are only
+    /// rendered by `build_string`, as `http::Uri`
+    /// does not retain them.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("app.fqdm.org")
+    ///     .with_fragment_path("/dashboard")
+    ///     .with_fragment_param("tab", 2);
+    /// assert_eq!(route.build_string(), "https://app.fqdm.org/#/dashboard?tab=2")
+    /// ```
+    fn with_fragment_path(mut self, path: &'a str) -> Self {
+        self.fragment_path = Some(percent_encode(path, "/"));
+        self
+    }
+
+    /// Add a parameter key/pair to the fragment.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("app.fqdm.org")
+    ///     .with_fragment_param("q", "a b");
+    /// assert_eq!(route.build_string(), "https://app.fqdm.org/#?q=a%20b")
+    /// ```
+    fn with_fragment_param<T: ToString>(mut self, name: &'a str, value: T) -> Self {
+        self.fragment_params.push(format!(
+            "{}={}",
+            percent_encode(name, ""),
+            percent_encode(&value.to_string(), "")));
+        self
+    }
+
+    /// Set the host of the builder, replacing
+    /// any existing host.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::default()
+    ///     .with_host("fqdm.org")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org")
+    /// ```
+    ///
+    /// One template can be fanned out across
+    /// multiple hosts.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let template = || ApiRouteBuilder::new("localhost")
+    ///     .with_path("status".into())
+    ///     .with_param("verbose".into(), 1);
+    ///
+    /// let route0 = template().with_host("node0.fqdm.org").build().unwrap();
+    /// let route1 = template().with_host("node1.fqdm.org").build().unwrap();
+    /// assert_eq!(route0, "https://node0.fqdm.org/status?verbose=1");
+    /// assert_eq!(route1, "https://node1.fqdm.org/status?verbose=1");
+    /// ```
+    ///
+    /// With the optional `idna` feature, Unicode
+    /// hosts are mapped and encoded as described
+    /// by UTS 46. Paths and parameters are not.
+    #[cfg_attr(feature = "idna", doc = r#"
+```rust
+use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+let route = ApiRouteBuilder::default()
+    .with_host("münchen.de")
+    .with_path("straße".into())
+    .build()
+    .unwrap();
+assert_eq!(route, "https://xn--mnchen-3ya.de/stra%C3%9Fe");
+
+let route = ApiRouteBuilder::new("mu\u{308}nchen.de").build().unwrap();
+assert_eq!(route, "https://xn--mnchen-3ya.de");
+
+let route = ApiRouteBuilder::new("Bücher.例え.jp").build().unwrap();
+assert_eq!(route, "https://xn--bcher-kva.xn--r8jz45g.jp")
+```
+"#)]
+    fn with_host(mut self, host: &'a str) -> Self {
+        self.hostname = Cow::Borrowed(host);
+        self
+    }
+
+    /// Add an already encoded path argument to
+    /// the end of the path buffer, bypassing
+    /// percent-encoding.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path_raw("signed%2Fkey")
+    ///     .with_path("a b".into())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org/signed%2Fkey/a%20b")
+    /// ```
+    fn with_path_raw(self, path: &'a str) -> Self {
+        self.insert_route_path(ApiRoutePath::new_encoded(path.to_owned(), f32::MAX))
+    }
+
+    /// Number of path segments, excluding the
+    /// root, the built URI will contain.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("".into())
+    ///     .with_path("a/b".into())
+    ///     .with_path("c".into());
+    /// assert_eq!(route.segment_count(), 3);
+    ///
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_separator(".")
+    ///     .with_paths(["a", "b", "c"]);
+    /// assert_eq!(route.segment_count(), 3)
+    /// ```
+    fn segment_count(&self) -> usize {
+        self.parse_path()
+            .split('/')
+            .flat_map(|s| s.split(self.separator))
+            .filter(|s| !s.is_empty())
+            .count()
+    }
+
+    /// Number of query parameters the built URI
+    /// will contain.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_param("page".into(), 1)
+    ///     .with_raw_query("q=a&sort=asc");
+    /// assert_eq!(route.param_count(), 3)
+    /// ```
+    fn param_count(&self) -> usize {
+        self.parse_query_entries().len()
+    }
+
+    /// A canonical key for the route, stable
+    /// regardless of parameter insertion order.
+    /// Unlike `build`, parameters are sorted.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route0 = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("resource".into())
+    ///     .with_param("b".into(), 2)
+    ///     .with_param("a".into(), 1);
+    /// let route1 = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("resource".into())
+    ///     .with_param("a".into(), 1)
+    ///     .with_param("b".into(), 2);
+    /// assert_eq!(route0.cache_key(), route1.cache_key());
+    /// assert_eq!(route0.cache_key(), "https://fqdm.org/resource?a=1&b=2");
+    ///
+    /// let route = ApiRouteBuilder::new("fqdm.org").with_path("x".into());
+    /// assert_eq!(route.cache_key(), "https://fqdm.org/x");
+    ///
+    /// let route = ApiRouteBuilder::new("h.org").with_scheme_relative();
+    /// assert_eq!(route.cache_key(), route.build_string())
+    /// ```
+    fn cache_key(&self) -> String {
+        let mut params = self.parse_query_entries();
+        params.sort();
+
+        let key = format!("{}{}", self.parse_origin(), self.parse_path());
+        match params.is_empty() {
+            true  => key,
+            false => format!("{key}?{}", params.join(&self.param_separator.to_string()))
+        }
+    }
+
+    /// Merges another builder onto the end of
+    /// this one. Paths of `other` follow the
+    /// paths of this builder, and its parameters,
+    /// scheme, host, fragment and separators
+    /// override those of this builder when set.
+    /// Options, such as a trailing slash, apply
+    /// when enabled on either.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let base = ApiRouteBuilder::new("api.fqdm.org")
+    ///     .with_path("v1".into())
+    ///     .with_param("key".into(), "abc")
+    ///     .with_param("page".into(), 1);
+    /// let overlay = ApiRouteBuilder::default()
+    ///     .with_path_weight("users".into(), 1.0)
+    ///     .with_path_weight("list".into(), 2.0)
+    ///     .with_param("page".into(), 2);
+    ///
+    /// let route = base.join(overlay).build().unwrap();
+    /// assert_eq!(route, "https://api.fqdm.org/v1/users/list?key=abc&page=2");
+    ///
+    /// let overlay = ApiRouteBuilder::default()
+    ///     .with_path("users".into())
+    ///     .with_fragment_path("/f")
+    ///     .with_trailing_slash(true);
+    /// let route = ApiRouteBuilder::new("fqdm.org").join(overlay);
+    /// assert_eq!(route.build_string(), "https://fqdm.org/users/#/f")
+    /// ```
+    fn join(mut self, mut other: Self) -> Self {
+        other.sort_paths();
+        let mut weight = self.last_weight();
+        for mut path in other.sub_paths.into_iter().filter(|p| !p.is_root()) {
+            weight += 1.0;
+            path.weight = OrderedFloat(weight);
+            self = self.insert_route_path(path);
+        }
+
+        let names: Vec<_> = other.parameters.iter().map(|p| p.name.clone()).collect();
+        self.parameters.retain(|p| !names.contains(&p.name));
+        self.parameters.extend(other.parameters);
+
+        if !other.hostname.is_empty() {
+            self.hostname = other.hostname;
+        }
+        if other.fragment_path.is_some() || !other.fragment_params.is_empty() {
+            self.fragment_path   = other.fragment_path;
+            self.fragment_params = other.fragment_params;
+        }
+        if other.param_separator != '&' {
+            self.param_separator = other.param_separator;
+        }
+        if other.separator != "/" {
+            self.separator = other.separator;
+        }
+        self.sorted_params   |= other.sorted_params;
+        self.space_as_plus   |= other.space_as_plus;
+        self.strict_segments |= other.strict_segments;
+        self.trailing_slash  |= other.trailing_slash;
+        self.authority       = other.authority.or(self.authority);
+        self.port            = other.port.or(self.port);
+        self.raw_query       = other.raw_query.or(self.raw_query);
+        self.scheme          = other.scheme.or(self.scheme);
+        self.scheme_default  = other.scheme_default.or(self.scheme_default);
+        self.scheme_fallback = other.scheme_fallback.or(self.scheme_fallback);
+        self.userinfo        = other.userinfo.or(self.userinfo);
+        self
+    }
+
+    /// Removes path arguments which render the
+    /// same as the path argument before them,
+    /// after sorting by weight.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_paths(["api", "api", "users", "api"])
+    ///     .dedup_adjacent()
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org/api/users/api");
+    ///
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path_matrix("seg", &[("a", "1")])
+    ///     .with_path_matrix("seg", &[("a", "2")])
+    ///     .dedup_adjacent();
+    /// assert_eq!(route.build_string(), "https://fqdm.org/seg;a=1/seg;a=2")
+    /// ```
+    fn dedup_adjacent(mut self) -> Self {
+        self.sort_paths();
+        self.sub_paths.dedup_by(|a, b| a.is_root() == b.is_root() && a.render() == b.render());
+        self
+    }
+
+    /// Set the port of the authority. The port is
+    /// omitted when it is the default port of the
+    /// scheme.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("localhost")
+    ///     .with_port(8080)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://localhost:8080");
+    ///
+    /// let route = |port| ApiRouteBuilder::new("localhost")
+    ///     .with_scheme("http".into())
+    ///     .with_port(port)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route(80), "http://localhost");
+    /// assert_eq!(route(8080), "http://localhost:8080")
+    /// ```
+    fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Set the userinfo of the authority. Unlike
+    /// the host, the userinfo retains its case.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("FQDM.org")
+    ///     .with_userinfo("Admin")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route.to_string(), "https://Admin@fqdm.org/")
+    /// ```
+    fn with_userinfo(mut self, userinfo: &'a str) -> Self {
+        self.userinfo = Some(userinfo.to_owned());
+        self
+    }
+
+    /// The authority, `[userinfo@]host[:port]`,
+    /// of the built URI.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org");
+    /// assert_eq!(route.authority(), "fqdm.org");
+    ///
+    /// let route = route.with_port(8080);
+    /// assert_eq!(route.authority(), "fqdm.org:8080");
+    ///
+    /// let route = route.with_userinfo("user");
+    /// assert_eq!(route.authority(), "user@fqdm.org:8080");
+    /// ```
+    fn authority(&self) -> String {
+        self.parse_authority(&self.parse_scheme())
+    }
+
+    /// Decomposes the builder into its owned
+    /// parts. The root path is excluded, and
+    /// parameters are decoded, with flags having
+    /// no value.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_port(8080)
+    ///     .with_path("users".into())
+    ///     .with_path_weight("api".into(), 1.0)
+    ///     .with_param("page".into(), 2)
+    ///     .with_param("q".into(), "a b")
+    ///     .with_flag("v");
+    /// let expected = route.build_string();
+    ///
+    /// let parts = route.into_parts();
+    /// assert_eq!(parts.params[1], ("q".to_owned(), Some("a b".to_owned())));
+    /// assert_eq!(parts.params[2], ("v".to_owned(), None));
+    ///
+    /// let mut rebuilt = ApiRouteBuilder::new(&parts.host)
+    ///     .with_port(parts.port.unwrap());
+    /// for (path, weight) in &parts.paths {
+    ///     rebuilt = rebuilt.with_path_weight(path.clone(), *weight);
+    /// }
+    /// for (name, value) in &parts.params {
+    ///     rebuilt = match value {
+    ///         Some(value) => rebuilt.with_param(name.clone(), value),
+    ///         None        => rebuilt.with_flag(name)
+    ///     };
+    /// }
+    /// assert_eq!(rebuilt.build_string(), expected);
+    /// assert_eq!(expected, "https://fqdm.org:8080/api/users?page=2&q=a%20b&v")
+    /// ```
+    fn into_parts(mut self) -> RouteParts {
+        self.sort_paths();
+        let mut params: Vec<_> = self.parameters
+            .iter()
+            .map(|p| match p.raw {
+                true  => (p.name.clone(), p.value.as_deref().map(percent_decode)),
+                false => (p.name.clone(), p.value.clone())
+            })
+            .collect();
+        if let Some(query) = self.raw_query {
+            params.extend(query
+                .trim_start_matches('?')
+                .split(self.param_separator)
+                .filter(|p| !p.is_empty())
+                .map(decode_param));
+        }
+        if self.sorted_params {
+            params.sort();
+        }
+
+        RouteParts{
+            scheme: self.scheme,
+            host: self.hostname.into_owned(),
+            port: self.port,
+            paths: self.sub_paths
+                .into_iter()
+                .filter(|p| !p.is_root())
+                .map(|p| (p.path, p.weight.into_inner()))
+                .collect(),
+            params
+        }
+    }
+
+    /// Set the character which separates query
+    /// parameters. Defaults to `&`.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_param_separator(';')
+    ///     .with_param("a".into(), 1)
+    ///     .with_param("b".into(), 2);
+    /// assert_eq!(route.build_string(), "https://fqdm.org/?a=1;b=2");
+    /// assert_eq!(route.param_count(), 2)
+    /// ```
+    fn with_param_separator(mut self, sep: char) -> Self {
+        self.param_separator = sep;
+        self
+    }
+
+    /// Checks the host is not empty, contains no
+    /// illegal characters and does not start or
+    /// end with a dot. Run before every build.
+    /// Userinfo given with the host, as in
+    /// `user@host`, is accepted and not checked;
+    /// prefer `with_userinfo`, which takes
+    /// precedence over it.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder, RouteBuildError};
+    /// assert!(ApiRouteBuilder::new("example.com").validate_host().is_ok());
+    /// assert!(ApiRouteBuilder::new("[::1]").validate_host().is_ok());
+    ///
+    /// let error = ApiRouteBuilder::new("exa mple.com").build();
+    /// assert!(matches!(error, Err(RouteBuildError::InvalidHost(..))));
+    ///
+    /// let error = ApiRouteBuilder::new("").validate_host();
+    /// assert_eq!(error.unwrap_err().to_string(), "invalid host \"\": host is empty");
+    ///
+    /// let error = ApiRouteBuilder::new(".example.com").validate_host();
+    /// assert!(matches!(error, Err(RouteBuildError::InvalidHost(..))));
+    ///
+    /// let route = ApiRouteBuilder::new("user@example.com").build().unwrap();
+    /// assert_eq!(route, "https://user@example.com");
+    ///
+    /// let route = ApiRouteBuilder::new("user@example.com").with_userinfo("admin");
+    /// assert_eq!(route.build().unwrap(), "https://admin@example.com")
+    /// ```
+    fn validate_host(&self) -> Result<(), RouteBuildError> {
+        let (_, host) = self.split_userinfo();
+        let error = |reason: &str| Err(RouteBuildError::InvalidHost(host.to_owned(), reason.to_owned()));
+
+        if host.is_empty() {
+            error("host is empty")
+        } else if host.starts_with('.') || host.ends_with('.') {
+            error("host starts or ends with a dot")
+        } else if let Some(c) = self.parse_host().chars().find(|c| !is_host_char(*c)) {
+            error(&format!("host contains illegal character {c:?}"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Set whether the path ends with exactly one
+    /// slash. Disabled by default.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = || ApiRouteBuilder::new("fqdm.org")
+    ///     .with_paths(["a", "b"])
+    ///     .with_param("page".into(), 1);
+    ///
+    /// assert_eq!(route().with_trailing_slash(false).build_string(), "https://fqdm.org/a/b?page=1");
+    /// assert_eq!(route().with_trailing_slash(true).build_string(), "https://fqdm.org/a/b/?page=1");
+    /// ```
+    fn with_trailing_slash(mut self, enabled: bool) -> Self {
+        self.trailing_slash = enabled;
+        self
+    }
+
+    /// Rewrites each path argument, other than
+    /// the root, preserving their weights.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_paths(["Users", "ReadMe"])
+    ///     .map_paths(|p| p.to_lowercase())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org/users/readme")
+    /// ```
+    fn map_paths(mut self, f: impl Fn(&str) -> String) -> Self {
+        for path in self.sub_paths.iter_mut().filter(|p| !p.is_root()) {
+            path.path = f(&path.path);
+        }
+        self
+    }
+
+    /// Whether the rendered URI equals another,
+    /// ignoring differences in percent-encoding
+    /// of unreserved characters and hex case.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path_raw("%7Euser")
+    ///     .with_path_raw("a%2fb");
+    /// assert!(route.semantic_eq("https://fqdm.org/~user/a%2Fb"));
+    /// assert!(!route.semantic_eq("https://fqdm.org/~user/a/b"))
+    /// ```
+    fn semantic_eq(&self, other: &str) -> bool {
+        normalize_encoding(&self.build_string()) == normalize_encoding(other)
+    }
+
+    /// Add a parameter without a value.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_flag("verbose")
+    ///     .with_param("page".into(), 1)
+    ///     .with_flag("raw");
+    /// assert_eq!(route.build_string(), "https://fqdm.org/?verbose&page=1&raw")
+    /// ```
+    fn with_flag(mut self, name: &'a str) -> Self {
+        self.parameters.push(ApiRouteParam{name: name.to_owned(), value: None, raw: false});
+        self
+    }
+
+    /// Removes a leading prefix from the path,
+    /// doing nothing if the path does not start
+    /// with it. The prefix is matched by whole,
+    /// decoded segments.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = || ApiRouteBuilder::new("fqdm.org").with_paths(["api", "users"]);
+    /// assert_eq!(route().strip_prefix("/api").build_string(), "https://fqdm.org/users");
+    /// assert_eq!(route().strip_prefix("/ap").build_string(), "https://fqdm.org/api/users");
+    ///
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("a b".into())
+    ///     .with_path_raw("c%20d")
+    ///     .with_path("e".into());
+    /// assert_eq!(route.strip_prefix("/a%20b/c%20d").build_string(), "https://fqdm.org/e")
+    /// ```
+    fn strip_prefix(mut self, prefix: &str) -> Self {
+        self.sort_paths();
+        let prefix: Vec<_> = prefix
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(percent_decode)
+            .collect();
+        let segments: Vec<_> = self.sub_paths
+            .iter()
+            .filter(|p| !p.is_root())
+            .flat_map(|p| p.path.split('/').map(|s| match p.encoded {
+                true  => percent_decode(s),
+                false => s.to_owned()
+            }))
+            .filter(|s| !s.is_empty())
+            .collect();
+        if !segments.starts_with(&prefix) {
+            return self;
+        }
+
+        let mut remaining = prefix.len();
+        self.sub_paths.retain_mut(|p| {
+            if remaining == 0 || p.is_root() {
+                return true;
+            }
+            let segments: Vec<_> = p.path
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .collect();
+            match segments.len() <= remaining {
+                true  => { remaining -= segments.len(); false },
+                false => {
+                    p.path = segments[remaining..].join("/");
+                    remaining = 0;
+                    true
+                }
+            }
+        });
+        self
+    }
+
+    /// Emit query parameters sorted by name, then
+    /// value, rather than in insertion order.
+    /// Parameters from a raw query are sorted
+    /// along with the rest.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = || ApiRouteBuilder::new("fqdm.org")
+    ///     .with_param("b".into(), 2)
+    ///     .with_param("a".into(), 1);
+    /// assert_eq!(route().build_string(), "https://fqdm.org/?b=2&a=1");
+    /// assert_eq!(route().with_sorted_params().build_string(), "https://fqdm.org/?a=1&b=2")
+    /// ```
+    fn with_sorted_params(mut self) -> Self {
+        self.sorted_params = true;
+        self
+    }
+
+    /// Removes every query parameter. A raw
+    /// query, if set, is kept.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_param("page".into(), 1)
+    ///     .clear_params()
+    ///     .with_param("page".into(), 2);
+    /// assert_eq!(route.build_string(), "https://fqdm.org/?page=2")
+    /// ```
+    fn clear_params(mut self) -> Self {
+        self.parameters.clear();
+        self
+    }
+
+    /// Removes every path argument, other than
+    /// the root.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_paths(["api", "users"])
+    ///     .clear_paths()
+    ///     .with_path("posts".into());
+    /// assert_eq!(route.build_string(), "https://fqdm.org/posts")
+    /// ```
+    fn clear_paths(mut self) -> Self {
+        self.sub_paths = vec![ApiRoutePath::root()];
+        self.paths_sorted = true;
+        self
+    }
+
+    /// Add a path argument before all others,
+    /// after the root. It is weighted lower than
+    /// the lowest weighted path argument.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("users".into())
+    ///     .prepend_path("api")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org/api/users");
+    ///
+    /// let mut route = ApiRouteBuilder::new("fqdm.org").with_path_weight("last".into(), 0.1);
+    /// for i in 0..200 {
+    ///     route = route.prepend_path(if i == 199 { "first" } else { "p" });
+    /// }
+    /// assert_eq!(route.segment_count(), 201);
+    /// assert!(route.build_string().starts_with("https://fqdm.org/first/p/"));
+    /// assert!(route.build_string().ends_with("/p/last"))
+    /// ```
+    fn prepend_path(mut self, path: &'a str) -> Self {
+        let lowest = self.sub_paths
+            .iter()
+            .filter(|p| !p.is_root())
+            .min()
+            .map(|p| p.weight());
+        let weight = match lowest {
+            None                     => 1.0,
+            Some(w) if w / 2.0 > 0.0 => w / 2.0,
+            Some(_)                  => {
+                // No room is left below the lowest
+                // weight, so space out those below 1.0.
+                self.sort_paths();
+                let mut low: Vec<_> = self.sub_paths
+                    .iter_mut()
+                    .filter(|p| !p.is_root() && p.weight() < 1.0)
+                    .collect();
+                let count = low.len() as f32 + 1.0;
+                for (i, p) in low.iter_mut().enumerate() {
+                    p.weight = OrderedFloat((i as f32 + 1.0) / count);
+                }
+                0.5 / count
+            }
+        };
+        self.insert_route_path(ApiRoutePath::new(path.to_owned(), weight))
+    }
+
+    /// Add a parameter only when a value is
+    /// given.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_param_opt("page", None::<u32>)
+    ///     .with_param_opt("limit", Some(5));
+    /// assert_eq!(route.build_string(), "https://fqdm.org/?limit=5")
+    /// ```
+    fn with_param_opt<T: ToString>(self, name: &'a str, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.insert_param(name.to_owned(), value),
+            None        => self
+        }
+    }
+
+    /// Add a path argument to the end of the
+    /// path buffer, leaving the characters in
+    /// `safe` unencoded along with `/`.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path_safe("items[0] a", "[]");
+    /// assert_eq!(route.build_string(), "https://fqdm.org/items[0]%20a")
+    /// ```
+    fn with_path_safe(self, path: &'a str, safe: &'a str) -> Self {
+        let mut path = ApiRoutePath::new(path.to_owned(), f32::MAX);
+        path.safe.push_str(safe);
+        self.insert_route_path(path)
+    }
+
+    /// Tries to build a URI no longer than the
+    /// given number of bytes. The length is
+    /// checked before the URI is built.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder, RouteBuildError};
+    /// let route = || ApiRouteBuilder::new("fqdm.org").with_path("resource".into());
+    /// assert_eq!(route().build_checked(2048).unwrap(), "https://fqdm.org/resource");
+    ///
+    /// let error = route().build_checked(16);
+    /// assert!(matches!(error, Err(RouteBuildError::TooLong{ len: 25, max: 16 })))
+    /// ```
+    fn build_checked(self, max_len: usize) -> Result<uri::Uri, RouteBuildError> {
+        let len = self.build_string().len();
+        match len > max_len {
+            true  => Err(RouteBuildError::TooLong{ len, max: max_len }),
+            false => self.build()
+        }
+    }
+
+    /// Each path argument, other than the root,
+    /// in the order they are built, paired with
+    /// the order they were added and their weight.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path_weight("list".into(), 3.0)
+    ///     .with_path_weight("api".into(), 1.0)
+    ///     .with_path_weight("users".into(), 2.0);
+    /// assert_eq!(route.debug_trace(), vec![
+    ///     (1, "api".into(), 1.0),
+    ///     (2, "users".into(), 2.0),
+    ///     (0, "list".into(), 3.0),
+    /// ])
+    /// ```
+    fn debug_trace(&self) -> Vec<(usize, String, f32)> {
+        let mut paths = self.sub_paths.clone();
+        paths.sort();
+        paths
+            .into_iter()
+            .filter(|p| !p.is_root())
+            .map(|p| (p.index, p.path, p.weight.into_inner()))
+            .collect()
+    }
+
+    /// Set an opaque scheme, such as `mailto`,
+    /// and its content, replacing the path. The
+    /// content is rendered as is, without an
+    /// authority. Content added afterwards with
+    /// `with_path` is percent-encoded.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::default().with_opaque("mailto", "user@example.com");
+    /// assert_eq!(route.build_string(), "mailto:user@example.com");
+    ///
+    /// let route = ApiRouteBuilder::default().with_opaque("tel", "+15551234");
+    /// assert_eq!(route.build_string(), "tel:+15551234");
+    ///
+    /// let route = ApiRouteBuilder::default()
+    ///     .with_scheme("data".into())
+    ///     .with_path("text/plain,hello world".into());
+    /// assert_eq!(route.build_string(), "data:text/plain,hello%20world")
+    /// ```
+    fn with_opaque(self, scheme: &'a str, content: &'a str) -> Self {
+        self.clear_paths()
+            .insert_scheme(Some(scheme.to_owned()))
+            .with_path_raw(content)
+    }
+
+    /// Set the string which separates path
+    /// arguments. Defaults to `/`. The path
+    /// always starts with `/`.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_separator(".")
+    ///     .with_paths(["a", "b", "c"]);
+    /// assert_eq!(route.build_string(), "https://fqdm.org/a.b.c");
+    ///
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_separator(".")
+    ///     .with_paths(["v1..2", "b"]);
+    /// assert_eq!(route.build_string(), "https://fqdm.org/v1..2.b")
+    /// ```
+    fn with_separator(mut self, sep: &'a str) -> Self {
+        self.separator = sep;
+        self
+    }
+
+    /// Add an already encoded parameter value,
+    /// which bypasses percent-encoding. The name
+    /// is still encoded.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_param_raw("token", "a%2Bb")
+    ///     .with_param("q".into(), "a+b c");
+    /// assert_eq!(route.build_string(), "https://fqdm.org/?token=a%2Bb&q=a%2Bb%20c")
+    /// ```
+    fn with_param_raw<T: ToString>(self, name: &'a str, value: T) -> Self {
+        self.insert_param_raw(name, value)
+    }
+
+    /// Add a single parameter whose values are
+    /// joined by commas. Each value is encoded,
+    /// so commas within a value are escaped.
+    /// Unlike repeated calls to `with_param`,
+    /// the name appears only once.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org").with_param_csv("ids", [1, 2, 3]);
+    /// assert_eq!(route.build_string(), "https://fqdm.org/?ids=1,2,3");
+    ///
+    /// let route = ApiRouteBuilder::new("fqdm.org").with_param_csv("tags", ["a,b", "c d"]);
+    /// assert_eq!(route.build_string(), "https://fqdm.org/?tags=a%2Cb,c%20d")
+    /// ```
+    fn with_param_csv<T: ToString>(self, name: &'a str, values: impl IntoIterator<Item = T>) -> Self {
+        let values: Vec<_> = values
+            .into_iter()
+            .map(|v| percent_encode(&v.to_string(), ""))
+            .collect();
+        self.insert_param_raw(name, values.join(","))
+    }
+
+    /// Reports what would be built, and the
+    /// problems which would prevent it, without
+    /// building. URIs longer than 2048 bytes,
+    /// a common limit, are reported as too long.
+    /// Opaque schemes have no authority, and
+    /// neither it nor the path are checked.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder, RouteBuildError};
+    /// let explanation = ApiRouteBuilder::new("")
+    ///     .with_path("resource".into())
+    ///     .with_param("page".into(), 1)
+    ///     .explain();
+    /// assert_eq!(explanation.scheme, "https");
+    /// assert_eq!(explanation.path, "/resource");
+    /// assert_eq!(explanation.params, vec!["page=1"]);
+    /// assert!(matches!(explanation.problems[..], [RouteBuildError::InvalidHost(..)]));
+    ///
+    /// let explanation = ApiRouteBuilder::new("")
+    ///     .with_scheme("ht tp".into())
+    ///     .explain();
+    /// assert_eq!(explanation.problems.len(), 2);
+    ///
+    /// let explanation = ApiRouteBuilder::default()
+    ///     .with_opaque("mailto", "user@example.com")
+    ///     .explain();
+    /// assert_eq!(explanation.authority, "");
+    /// assert_eq!(explanation.path, "user@example.com");
+    /// assert!(matches!(explanation.problems[..], [RouteBuildError::OpaqueScheme(_)]))
+    /// ```
+    fn explain(&self) -> RouteExplanation {
+        let scheme = self.parse_scheme();
+        let opaque = is_opaque_scheme(&scheme);
+        let mut problems: Vec<_> = match opaque {
+            true  => vec![self.validate_scheme(scheme.clone()).err()],
+            false => vec![
+                self.validate_scheme(scheme.clone()).err(),
+                self.validate_authority(&scheme).err(),
+                self.validate_path().err(),
+            ]
+        }.into_iter().flatten().collect();
+
+        let len = self.build_string().len();
+        if len > 2048 {
+            problems.push(RouteBuildError::TooLong{ len, max: 2048 });
+        }
+
+        RouteExplanation{
+            authority: match opaque {
+                true  => String::new(),
+                false => self.parse_authority(&scheme)
+            },
+            path: self.parse_path(),
+            params: self.parse_query_entries(),
+            scheme,
+            problems
+        }
+    }
+
+    /// Set whether spaces in parameter values are
+    /// encoded as `+`, as in form encoding, rather
+    /// than `%20`. Disabled by default. Raw values
+    /// are left as they are.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = || ApiRouteBuilder::new("fqdm.org").with_param("q".into(), "a b+c");
+    /// assert_eq!(route().build_string(), "https://fqdm.org/?q=a%20b%2Bc");
+    /// assert_eq!(route().with_space_as_plus(true).build_string(), "https://fqdm.org/?q=a+b%2Bc")
+    /// ```
+    fn with_space_as_plus(mut self, enabled: bool) -> Self {
+        self.space_as_plus = enabled;
+        self
+    }
+
+    /// Set the whole authority, overriding the
+    /// host, port and userinfo. The authority is
+    /// used as is, and the host is not validated.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_port(443)
+    ///     .with_authority("user@host:8080")
+    ///     .with_path("resource".into())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://user@host:8080/resource")
+    /// ```
+    fn with_authority(mut self, authority: &'a str) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Keep only the parameters for which the
+    /// predicate, given each name and value,
+    /// holds. Flags have an empty value, and a
+    /// raw query is left as it is.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_param("_debug".into(), 1)
+    ///     .with_param("page".into(), 2)
+    ///     .with_flag("_trace")
+    ///     .retain_params(|name, _| !name.starts_with('_'));
+    /// assert_eq!(route.build_string(), "https://fqdm.org/?page=2")
+    /// ```
+    fn retain_params(mut self, f: impl Fn(&str, &str) -> bool) -> Self {
+        self.parameters.retain(|p| f(&p.name, p.value.as_deref().unwrap_or_default()));
+        self
+    }
+
+    /// Fail to build when any path argument,
+    /// other than the root, is empty or only
+    /// whitespace. By default these are dropped.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder, RouteBuildError};
+    /// let route = || ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("users".into())
+    ///     .with_path("".into())
+    ///     .with_path(" ".into());
+    /// assert_eq!(route().build().unwrap(), "https://fqdm.org/users");
+    ///
+    /// let error = route().with_strict_segments().build();
+    /// assert!(matches!(error, Err(RouteBuildError::InvalidPath(_))))
+    /// ```
+    fn with_strict_segments(mut self) -> Self {
+        self.strict_segments = true;
+        self
+    }
+
+    /// Set the scheme to `wss`, or `ws` when not
+    /// secure. As with `https` and `http`, their
+    /// default ports are omitted.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_scheme_ws(true)
+    ///     .with_port(443)
+    ///     .with_path("socket".into())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "wss://fqdm.org/socket");
+    ///
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_scheme_ws(false)
+    ///     .with_port(8080);
+    /// assert_eq!(route.build_string(), "ws://fqdm.org:8080/")
+    /// ```
+    fn with_scheme_ws(self, secure: bool) -> Self {
+        match secure {
+            true  => self.insert_scheme(Some(String::from("wss"))),
+            false => self.insert_scheme(Some(String::from("ws")))
+        }
+    }
+
+    /// Merge parameters into the builder,
+    /// resolving those which share a name by the
+    /// given strategy.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder, MergeStrategy};
+    /// let route = || ApiRouteBuilder::new("fqdm.org")
+    ///     .with_param("page".into(), 1)
+    ///     .with_param("sort".into(), "asc");
+    /// let other = [("page", "2"), ("limit", "10")];
+    ///
+    /// let merged = route().merge_params(&other, MergeStrategy::KeepExisting);
+    /// assert_eq!(merged.build_string(), "https://fqdm.org/?page=1&sort=asc&limit=10");
+    ///
+    /// let merged = route().merge_params(&other, MergeStrategy::Overwrite);
+    /// assert_eq!(merged.build_string(), "https://fqdm.org/?sort=asc&page=2&limit=10");
+    ///
+    /// let merged = route().merge_params(&other, MergeStrategy::AppendBoth);
+    /// assert_eq!(merged.build_string(), "https://fqdm.org/?page=1&sort=asc&page=2&limit=10")
+    /// ```
+    fn merge_params(mut self, other: &[(&str, &str)], strategy: MergeStrategy) -> Self {
+        for (name, value) in other {
+            let exists = self.parameters.iter().any(|p| p.name == *name);
+            match (strategy, exists) {
+                (MergeStrategy::KeepExisting, true) => continue,
+                (MergeStrategy::Overwrite, true)    => self.parameters.retain(|p| p.name != *name),
+                _                                   => ()
+            }
+            self = self.insert_param(name.to_string(), value);
+        }
+        self
+    }
+
+    /// Renders only the path and query, omitting
+    /// the scheme and authority.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_scheme("https".into())
+    ///     .with_port(8443)
+    ///     .with_path("a".into())
+    ///     .with_path("b".into())
+    ///     .with_param("x".into(), 1);
+    /// assert_eq!(route.relative_ref(), "/a/b?x=1");
+    /// assert_eq!(ApiRouteBuilder::new("fqdm.org").relative_ref(), "/")
+    /// ```
+    fn relative_ref(&self) -> String {
+        self.parse_path_and_query()
+    }
+
+    /// Insert a path argument between two
+    /// adjacent path arguments, weighted midway
+    /// between them. Where the two share a
+    /// weight, as paths added by `with_path` do,
+    /// the path arguments sharing it are first
+    /// spread out below it, in order. Other
+    /// weights are kept.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder, RouteBuildError};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_paths(["api", "users"])
+    ///     .insert_between("v2", "api", "users")
+    ///     .unwrap();
+    /// assert_eq!(route.build().unwrap(), "https://fqdm.org/api/v2/users");
+    ///
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("api".into())
+    ///     .with_path("users".into())
+    ///     .insert_between("v2", "api", "users")
+    ///     .unwrap()
+    ///     .with_path("list".into());
+    /// assert_eq!(route.build().unwrap(), "https://fqdm.org/api/v2/users/list");
+    ///
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path_weight("b".into(), 50.0)
+    ///     .with_path("api".into())
+    ///     .with_path("users".into())
+    ///     .insert_between("v2", "api", "users")
+    ///     .unwrap()
+    ///     .with_path_weight("a".into(), 10.0);
+    /// assert_eq!(route.build().unwrap(), "https://fqdm.org/a/b/api/v2/users");
+    ///
+    /// let error = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_paths(["api", "users"])
+    ///     .insert_between("v2", "api", "posts");
+    /// assert!(matches!(error, Err(RouteBuildError::PathNotFound(_))));
+    ///
+    /// let error = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_paths(["api", "users", "posts"])
+    ///     .insert_between("v2", "api", "posts");
+    /// assert!(matches!(error, Err(RouteBuildError::PathsNotAdjacent(..))));
+    /// ```
+    fn insert_between(mut self, path: &'a str, after: &str, before: &str) -> Result<Self, RouteBuildError> {
+        self.sort_paths();
+        let position = |name: &str| self.sub_paths
+            .iter()
+            .position(|p| p == name)
+            .ok_or(RouteBuildError::PathNotFound(name.to_owned()));
+        let (a, b) = (position(after)?, position(before)?);
+
+        if b != a + 1 {
+            return Err(RouteBuildError::PathsNotAdjacent(after.to_owned(), before.to_owned()))
+        }
+        let tied = self.sub_paths[a].weight;
+        if tied == self.sub_paths[b].weight {
+            let start = self.sub_paths.iter().position(|p| !p.is_root() && p.weight == tied).unwrap_or(a);
+            let end   = b + self.sub_paths[b..].iter().take_while(|p| p.weight == tied).count();
+            let floor = match self.sub_paths[start - 1].is_root() {
+                true  => 0.0,
+                false => self.sub_paths[start - 1].weight()
+            };
+            let count = (end - start) as f32;
+            for (i, p) in self.sub_paths[start..end].iter_mut().enumerate() {
+                p.weight = OrderedFloat(floor + (tied.into_inner() - floor) * ((i + 1) as f32 / count));
+            }
+        }
+
+        let (lower, upper) = (self.sub_paths[a].weight(), self.sub_paths[b].weight());
+        Ok(self.insert_path(path.to_owned(), Some(lower + (upper - lower) / 2.0)))
+    }
+
+    /// Add a boolean parameter, rendered in the
+    /// given style.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder, BoolStyle};
+    /// let route = ApiRouteBuilder::new("fqdm.org")
+    ///     .with_param_bool("a", true, BoolStyle::TrueFalse)
+    ///     .with_param_bool("b", false, BoolStyle::TrueFalse)
+    ///     .with_param_bool("c", true, BoolStyle::OneZero)
+    ///     .with_param_bool("d", false, BoolStyle::OneZero)
+    ///     .with_param_bool("e", true, BoolStyle::YesNo)
+    ///     .with_param_bool("f", false, BoolStyle::YesNo)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org/?a=true&b=false&c=1&d=0&e=yes&f=no")
+    /// ```
+    fn with_param_bool(self, name: &'a str, value: bool, style: BoolStyle) -> Self {
+        self.insert_param(name.to_owned(), style.render(value))
+    }
+
+    /// The path arguments as a relative
+    /// filesystem path, one component per
+    /// segment, with percent-encoding decoded.
+    /// Fails on segments which are not a single,
+    /// plain, path component.
+    /// ```rust
+    /// use std::path::{Component, PathBuf};
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder, RouteBuildError};
+    /// let path = ApiRouteBuilder::new("localhost")
+    ///     .with_scheme("file".into())
+    ///     .with_path("a".into())
+    ///     .with_path("b".into())
+    ///     .to_path_buf()
+    ///     .unwrap();
+    /// let components: Vec<_> = path.components().collect();
+    /// assert_eq!(components, [Component::Normal("a".as_ref()), Component::Normal("b".as_ref())]);
+    ///
+    /// let path = ApiRouteBuilder::new("localhost")
+    ///     .with_path("my files".into())
+    ///     .to_path_buf();
+    /// assert_eq!(path.unwrap(), PathBuf::from("my files"));
+    ///
+    /// let error = ApiRouteBuilder::new("localhost")
+    ///     .with_path_raw("..%2F..%2Fetc")
+    ///     .to_path_buf();
+    /// assert!(matches!(error, Err(RouteBuildError::InvalidPath(_))));
+    ///
+    /// let error = ApiRouteBuilder::new("localhost")
+    ///     .with_path("..".into())
+    ///     .to_path_buf();
+    /// assert!(matches!(error, Err(RouteBuildError::InvalidPath(_))))
+    /// ```
+    fn to_path_buf(&self) -> Result<PathBuf, RouteBuildError> {
+        self.parse_path()
+            .split('/')
+            .flat_map(|s| s.split(self.separator))
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                let segment = percent_decode(s);
+                let mut components = Path::new(&segment).components();
+                match (segment.contains(['/', '\\']), components.next(), components.next()) {
+                    (false, Some(Component::Normal(_)), None) => Ok(segment),
+                    _                                         => Err(RouteBuildError::InvalidPath(segment))
+                }
+            })
+            .collect()
+    }
+}
+
+impl<'a> ApiRouteBuilder<'a> {
+    /// Freezes this builder into a factory of
+    /// builders sharing this configuration.
+    pub fn freeze(mut self) -> RouteFactory<'a> {
+        self.sort_paths();
+        RouteFactory{base: Arc::new(self)}
+    }
+
+    /// Creates a builder with its host read from
+    /// an environment variable.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// std::env::set_var("URI_ROUTES_FROM_ENV_HOST", "fqdm.org");
+    /// let route = ApiRouteBuilder::from_env("URI_ROUTES_FROM_ENV_HOST").unwrap();
+    /// assert_eq!(route.build().unwrap(), "https://fqdm.org");
+    ///
+    /// std::env::remove_var("URI_ROUTES_FROM_ENV_HOST");
+    /// assert!(ApiRouteBuilder::from_env("URI_ROUTES_FROM_ENV_HOST").is_err())
+    /// ```
+    pub fn from_env(var: &str) -> Result<Self, std::env::VarError> {
+        let host = std::env::var(var)?;
+        Ok(Self{hostname: Cow::Owned(host), ..Self::new("")})
+    }
+
+    /// Creates a builder from an existing URI.
+    /// Path arguments and query parameters are
+    /// split apart. Path arguments are decoded,
+    /// and are encoded consistently when rebuilt,
+    /// with a `/` encoded within one staying
+    /// encoded. Parameter values keep their
+    /// original encoding, so `+` and other
+    /// reserved characters keep their meaning.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let uri = "http://fqdm.org:8080/my%20files/a%2Fb/list?a=1&b=x%26y&c".parse().unwrap();
+    /// let route = ApiRouteBuilder::from_uri(&uri).unwrap();
+    /// assert_eq!(route.segment_count(), 3);
+    /// assert_eq!(route.param_count(), 3);
+    /// assert_eq!(route.build().unwrap(), uri);
+    ///
+    /// let uri = "https://fqdm.org/a;v=1/b:c?q=a+b&r=http://y".parse().unwrap();
+    /// let route = ApiRouteBuilder::from_uri(&uri).unwrap();
+    /// assert_eq!(route.param_count(), 2);
+    /// assert_eq!(route.build().unwrap(), uri);
+    ///
+    /// let uri = "/relative?a=1".parse().unwrap();
+    /// assert!(ApiRouteBuilder::from_uri(&uri).is_err())
+    /// ```
+    pub fn from_uri(uri: &uri::Uri) -> Result<Self, RouteBuildError> {
+        let authority = uri
+            .authority()
+            .ok_or(RouteBuildError::InvalidHost(uri.to_string(), "missing host".into()))?;
+
+        let mut builder = Self{hostname: Cow::Owned(authority.host().to_owned()), ..Self::new("")};
+        builder.scheme   = uri.scheme_str().map(String::from);
+        builder.port     = authority.port_u16();
+        builder.userinfo = authority
+            .as_str()
+            .rsplit_once('@')
+            .map(|(userinfo, _)| userinfo.to_owned());
+
+        let mut weight = builder.last_weight();
+        for segment in uri.path().split('/').filter(|p| !p.is_empty()) {
+            weight += 1.0;
+            let mut path = ApiRoutePath::new(percent_decode(segment), weight);
+            path.safe.clear();
+            builder = builder.insert_route_path(path);
+        }
+
+        for param in uri.query().unwrap_or("").split('&').filter(|p| !p.is_empty()) {
+            let (name, value) = match param.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_owned())),
+                None                => (param, None)
+            };
+            builder.parameters.push(ApiRouteParam{name: percent_decode(name), value, raw: true});
+        }
+        Ok(builder)
+    }
+
+    /// Sign the path and query with HMAC-SHA256,
+    /// appending the hex digest as a parameter.
+    /// Parameters are sorted, so the signature is
+    /// computed over the exact path and query
+    /// which are built, less the signature itself.
+    /// Changes made after signing are not covered.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = |page: u32| ApiRouteBuilder::new("fqdm.org")
+    ///     .with_path("files".into())
+    ///     .with_param("page".into(), page)
+    ///     .with_param("expires".into(), 1700000000)
+    ///     .sign(b"secret", "sig")
+    ///     .build()
+    ///     .unwrap()
+    ///     .to_string();
+    /// assert_eq!(route(1), route(1));
+    /// assert_ne!(route(1), route(2));
+    ///
+    /// let signed = route(1);
+    /// let (unsigned, signature) = signed.split_once("&sig=").unwrap();
+    /// assert_eq!(unsigned, "https://fqdm.org/files?expires=1700000000&page=1");
+    /// assert_eq!(signature.len(), 64)
+    /// ```
+    #[cfg(feature = "hmac")]
+    pub fn sign(mut self, key: &[u8], param_name: &str) -> Self {
+        self.sorted_params = true;
+        let mut mac = hmac::Hmac::<sha2::Sha256>::new_from_slice(key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(self.parse_path_and_query().as_bytes());
+        let signature: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        self.insert_param_raw(param_name, signature)
+    }
+
+    /// Creates a builder with room for the given
+    /// number of path arguments and parameters.
+    /// Path arguments are sorted by weight once,
+    /// when rendered, so they build in the same
+    /// order regardless of how they were added.
+    /// ```rust
+    /// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+    /// let route = ApiRouteBuilder::with_capacity("fqdm.org", 3, 1)
+    ///     .with_path_weight("list".into(), 3.0)
+    ///     .with_path_weight("api".into(), 1.0)
+    ///     .with_path_weight("users".into(), 2.0)
+    ///     .with_param("page".into(), 1)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(route, "https://fqdm.org/api/users/list?page=1")
+    /// ```
+    pub fn with_capacity(host: &'a str, paths: usize, params: usize) -> Self {
+        let mut builder = Self::new(host);
+        builder.sub_paths.reserve(paths);
+        builder.parameters.reserve(params);
+        builder
+    }
+}
+
+/// An immutable, shared, snapshot of a configured
+/// builder. Each call to `make` produces a fresh
+/// builder from the snapshot.
+/// ```rust
+/// use crate::uri_routes::{RouteBuilder, ApiRouteBuilder};
+/// let factory = ApiRouteBuilder::new("fqdm.org")
+///     .with_path("search".into())
+///     .freeze();
+/// let base = factory.base() as *const _;
+///
+/// for page in 0..1000 {
+///     let route = factory.make().with_param("page".into(), page);
+///     assert_eq!(route.build_string(), format!("https://fqdm.org/search?page={page}"));
+/// }
+/// assert_eq!(factory.base() as *const _, base);
+/// assert_eq!(factory.clone().base() as *const _, base);
+/// ```
+#[derive(Clone)]
+pub struct RouteFactory<'a> {
+    base: Arc<ApiRouteBuilder<'a>>,
+}
+
+impl<'a> RouteFactory<'a> {
+    /// The frozen builder shared by this factory.
+    pub fn base(&self) -> &ApiRouteBuilder<'a> {
+        &self.base
+    }
+
+    /// New builder from the frozen snapshot.
+    pub fn make(&self) -> ApiRouteBuilder<'a> {
+        self.base.as_ref().clone()
+    }
+}
+
+/// How boolean parameters are rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoolStyle {
+    /// `1` or `0`.
+    OneZero,
+    /// `true` or `false`.
+    TrueFalse,
+    /// `yes` or `no`.
+    YesNo,
+}
+
+impl BoolStyle {
+    /// Renders the value in this style.
+    fn render(self, value: bool) -> &'static str {
+        match (self, value) {
+            (Self::OneZero, true)    => "1",
+            (Self::OneZero, false)   => "0",
+            (Self::TrueFalse, true)  => "true",
+            (Self::TrueFalse, false) => "false",
+            (Self::YesNo, true)      => "yes",
+            (Self::YesNo, false)     => "no"
+        }
+    }
+}
+
+/// How parameters which share a name are
+/// merged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep every parameter, existing or not.
+    AppendBoth,
+    /// Keep the existing parameter, ignoring the
+    /// merged one.
+    KeepExisting,
+    /// Replace the existing parameter with the
+    /// merged one.
+    Overwrite,
+}
+
+/// What an `ApiRouteBuilder` would build, and
+/// the problems which would prevent it.
+#[derive(Debug)]
+pub struct RouteExplanation {
+    pub scheme:    String,
+    pub authority: String,
+    pub path:      String,
+    pub params:    Vec<String>,
+    pub problems:  Vec<RouteBuildError>,
+}
+
+/// The owned parts of an `ApiRouteBuilder`.
+#[derive(Clone, Debug)]
+pub struct RouteParts {
+    pub scheme: Option<String>,
+    pub host:   String,
+    pub port:   Option<u16>,
+    pub paths:  Vec<(String, f32)>,
+    pub params: Vec<(String, Option<String>)>,
+}
+
+/// Splits a query parameter into its decoded
+/// name and value, which is absent for flags.
+fn decode_param(param: &str) -> (String, Option<String>) {
+    match param.split_once('=') {
+        Some((name, value)) => (percent_decode(name), Some(percent_decode(value))),
+        None                => (percent_decode(param), None)
+    }
+}
+
+/// The port used by the scheme when none is
+/// given.
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http"  => Some(80),
+        "https" => Some(443),
+        "ws"    => Some(80),
+        "wss"   => Some(443),
+        _       => None
+    }
+}
+
+/// Percent-encodes `value` as a single path
+/// segment, escaping `/` and leaving the
+/// RFC 3986 sub-delimiters, `:` and `@` as
+/// they are.
+///
+/// ```rust
+/// use crate::uri_routes::{encode_path_segment, RouteBuilder, ApiRouteBuilder};
+///
+/// assert_eq!(encode_path_segment("a:b@c+d/e f"), "a:b@c+d%2Fe%20f");
+///
+/// let route = ApiRouteBuilder::new("fqdm.org").with_path("a:b@c+d".into());
+/// assert_eq!(route.build().unwrap().to_string(), format!("https://fqdm.org/{}", encode_path_segment("a:b@c+d")));
+/// ```
+pub fn encode_path_segment(value: &str) -> String {
+    percent_encode(value, PATH_SAFE)
+}
+
+/// Whether the character may appear in a host,
+/// including IPv6 literals.
+fn is_host_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._[]:".contains(c)
+}
+
+/// Whether the scheme has no authority and an
+/// opaque path.
+fn is_opaque_scheme(scheme: &str) -> bool {
+    matches!(scheme, "data" | "mailto" | "tel")
+}
+
+/// Decodes percent-encoded unreserved characters
+/// and uppercases the hex digits of all other
+/// percent-encodings.
+fn normalize_encoding(value: &str) -> String {
+    let mut normalized = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(i) = rest.find('%') {
+        normalized.push_str(&rest[..i]);
+        let escape = rest
+            .get(i + 1..i + 3)
+            .filter(|e| e.bytes().all(|b| b.is_ascii_hexdigit()));
+        match escape.and_then(|e| u8::from_str_radix(e, 16).ok()) {
+            Some(b) if b.is_ascii_alphanumeric() || b"-._~".contains(&b) => {
+                normalized.push(b as char);
+                rest = &rest[i + 3..];
+            },
+            Some(b) => {
+                normalized.push_str(&format!("%{b:02X}"));
+                rest = &rest[i + 3..];
+            },
+            None => {
+                normalized.push('%');
+                rest = &rest[i + 1..];
+            }
+        }
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+/// Decodes percent-encoded octets, replacing
+/// invalid UTF-8 sequences.
+fn percent_decode(value: &str) -> String {
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut i = 0;
+    while i < value.len() {
+        let escape = value
+            .get(i + 1..i + 3)
+            .filter(|e| e.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|e| u8::from_str_radix(e, 16).ok());
+        match (value.as_bytes()[i], escape) {
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            },
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Characters, other than unreserved ones, which
+/// RFC 3986 allows unencoded in a path segment.
+const PATH_SAFE: &str = "!$&'()*+,;=:@";
+
+/// Percent-encodes every character of `value`
+/// outside of the RFC 3986 unreserved set and
+/// the additional `safe` characters.
+fn percent_encode(value: &str, safe: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_ascii_alphanumeric() || "-._~".contains(c) || safe.contains(c) {
+            encoded.push(c);
+            continue;
+        }
+        let mut buf = [0; 4];
+        for b in c.encode_utf8(&mut buf).bytes() {
+            encoded.push_str(&format!("%{b:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Splits a query parameter into its name and
+/// value.
+fn split_param(param: &str) -> (&str, &str) {
+    param.split_once('=').unwrap_or((param, ""))
 }